@@ -0,0 +1,58 @@
+#[macro_use]
+extern crate criterion;
+extern crate multibase;
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+use multibase::{decode, encode, Base};
+
+/// Payload sizes for every base: a hash, a small record, and a blob. The
+/// big-number bases are quadratic, so they stop at the record, and are
+/// also measured on an ID short enough to fit a `u128`.
+fn sizes(base: Base) -> &'static [usize] {
+    if base.is_bitwise() {
+        &[32, 1024, 64 * 1024]
+    } else {
+        &[16, 32, 1024]
+    }
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7919 % 251) as u8 + 1).collect()
+}
+
+fn encoding(c: &mut Criterion) {
+    for &base in Base::ALL {
+        let mut group = c.benchmark_group(format!("encode/{}", base.name()));
+
+        for &len in sizes(base) {
+            let data = payload(len);
+
+            group.throughput(Throughput::Bytes(len as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(len), &data, |b, data| {
+                b.iter(|| encode(base, black_box(data)))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+fn decoding(c: &mut Criterion) {
+    for &base in Base::ALL {
+        let mut group = c.benchmark_group(format!("decode/{}", base.name()));
+
+        for &len in sizes(base) {
+            let encoded = encode(base, payload(len));
+
+            group.throughput(Throughput::Bytes(len as u64));
+            group.bench_with_input(BenchmarkId::from_parameter(len), &encoded, |b, encoded| {
+                b.iter(|| decode(black_box(encoded)))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, encoding, decoding);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+#[macro_use]
+extern crate criterion;
+extern crate multibase;
+
+use criterion::{black_box, Criterion};
+use multibase::{decode, Base};
+
+fn from_code(c: &mut Criterion) {
+    let codes: Vec<char> = Base::ALL.iter().map(Base::code).chain(vec!['!', '~']).collect();
+
+    c.bench_function("from_code/all", |b| {
+        b.iter(|| {
+            for &code in &codes {
+                let _ = black_box(Base::from_code(black_box(code)));
+            }
+        })
+    });
+
+    c.bench_function("from_code/base32", |b| b.iter(|| Base::from_code(black_box('b'))));
+    c.bench_function("from_code/base256emoji", |b| b.iter(|| Base::from_code(black_box('🚀'))));
+
+    // Prefix dispatch on a typical CID, where it is a noticeable share of
+    // the work.
+    let cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    c.bench_function("from_code/decode_cid", |b| b.iter(|| decode(black_box(cid))));
+}
+
+criterion_group!(benches, from_code);
+criterion_main!(benches);
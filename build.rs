@@ -0,0 +1,159 @@
+//! Generates the `Base` table from the upstream `multibase.csv`.
+//!
+//! The table is emitted as a `for_each_base!` macro that hands every entry
+//! to a callback macro, so the enum and anything else keyed by base are
+//! built from the same list.
+//!
+//! Codes, names and descriptions come straight from the spec table. Rows
+//! are only turned into `Base` variants once an alphabet for them is listed
+//! in `ALPHABETS`, so new spec entries are picked up by adding one line here.
+//!
+//! Bases whose family feature is disabled, such as `base58` for
+//! `base58btc` and `base58flickr`, are left out of the table altogether.
+
+use std::collections::HashSet;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const ALPHABETS: &[(&str, &str)] = &[
+    ("base2", "01"),
+    ("base8", "01234567"),
+    ("base10", "0123456789"),
+    ("base16", "0123456789abcdef"),
+    ("base16upper", "0123456789ABCDEF"),
+    ("base32hex", "0123456789abcdefghijklmnopqrstuv"),
+    ("base32hexupper", "0123456789ABCDEFGHIJKLMNOPQRSTUV"),
+    ("base32hexpad", "0123456789abcdefghijklmnopqrstuv"),
+    ("base32hexpadupper", "0123456789ABCDEFGHIJKLMNOPQRSTUV"),
+    ("base32", "abcdefghijklmnopqrstuvwxyz234567"),
+    ("base32upper", "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+    ("base32pad", "abcdefghijklmnopqrstuvwxyz234567"),
+    ("base32padupper", "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567"),
+    ("base32z", "ybndrfg8ejkmcpqxot1uwisza345h769"),
+    ("base58flickr", "123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ"),
+    ("base58btc", "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"),
+    ("base64", "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+    ("base64pad", "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"),
+    ("base64url", "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"),
+    ("base64urlpad", "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"),
+];
+
+struct Entry {
+    name: String,
+    code: char,
+    description: String,
+    status: String,
+}
+
+fn parse(csv: &str) -> Vec<Entry> {
+    csv.lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.splitn(4, ',').map(str::trim).collect();
+            assert_eq!(fields.len(), 4, "malformed multibase.csv row: {}", line);
+
+            let code = if fields[1].starts_with("0x") {
+                let value = u32::from_str_radix(&fields[1][2..], 16).expect("invalid hex code");
+                std::char::from_u32(value).expect("invalid code point")
+            } else {
+                let mut chars = fields[1].chars();
+                let code = chars.next().expect("empty code");
+                assert!(chars.next().is_none(), "multi-character code: {}", fields[1]);
+                code
+            };
+
+            Entry {
+                name: fields[0].to_string(),
+                code,
+                description: fields[2].to_string(),
+                status: capitalize(fields[3]),
+            }
+        })
+        .collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut capitalized = word[..1].to_uppercase();
+    capitalized.push_str(&word[1..]);
+    capitalized
+}
+
+/// The cargo feature that enables a base: its name up to the end of the
+/// radix, e.g. `base32` for `base32hexupper`.
+fn feature(name: &str) -> &str {
+    let radix = name.trim_start_matches("base");
+    let len = radix.find(|c: char| !c.is_ascii_digit()).unwrap_or(radix.len());
+
+    &name[..name.len() - radix.len() + len]
+}
+
+/// Turn a spec name into a variant name, e.g. `base32hexupper` into
+/// `Base32hexUpper`.
+fn variant(name: &str) -> String {
+    let (stem, upper) = match name.strip_suffix("upper") {
+        Some(stem) => (stem, "Upper"),
+        None => (name, ""),
+    };
+
+    let mut variant = capitalize(stem);
+    variant.push_str(upper);
+    variant
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=multibase.csv");
+
+    let csv = fs::read_to_string("multibase.csv").expect("failed to read multibase.csv");
+    let entries = parse(&csv);
+
+    let mut names = HashSet::new();
+    let mut codes = HashSet::new();
+
+    for entry in &entries {
+        assert!(names.insert(&entry.name), "duplicate name: {}", entry.name);
+        assert!(codes.insert(entry.code), "duplicate code: {:?}", entry.code);
+    }
+
+    for &(name, _) in ALPHABETS {
+        assert!(names.contains(&name.to_string()), "{} is not in multibase.csv", name);
+    }
+
+    let mut out = String::from("macro_rules! for_each_base {\n    ($callback:ident) => {\n        $callback! {\n");
+
+    for entry in &entries {
+        let alphabet = match ALPHABETS.iter().find(|&&(name, _)| name == entry.name) {
+            Some(&(_, alphabet)) => alphabet,
+            None => continue,
+        };
+
+        let enabled = format!("CARGO_FEATURE_{}", feature(&entry.name).to_uppercase());
+
+        if env::var_os(enabled).is_none() {
+            continue;
+        }
+
+        let padded = entry.name.contains("pad");
+
+        writeln!(out, "            #[doc = {:?}]", entry.description).unwrap();
+        writeln!(
+            out,
+            "            {:?} => {}, {}: {:?}, b{:?} ({}, {}),",
+            entry.code,
+            variant(&entry.name),
+            entry.name,
+            entry.name,
+            alphabet,
+            entry.status,
+            padded
+        ).unwrap();
+    }
+
+    out.push_str("        }\n    };\n}\n");
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("bases.rs");
+    fs::write(dest, out).expect("failed to write the base table");
+}
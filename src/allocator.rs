@@ -0,0 +1,65 @@
+//! Output in custom allocators, through the unstable `allocator_api`.
+
+use alloc::alloc::Allocator;
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "decode")]
+use decodable::split;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(feature = "decode")]
+use {Decodable, Result};
+use Base;
+
+/// Encode with the given base into a vector in `alloc`, such as an arena.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use multibase::{Base, encode_in};
+///
+/// assert_eq!(encode_in(Base::Base58btc, b"hello", Global), b"zCn8eVZg");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_in<T: AsRef<[u8]>, A: Allocator>(base: Base, data: T, alloc: A) -> Vec<u8, A> {
+    let data = data.as_ref();
+    let len = base.max_encoded_len(data.len());
+    let mut encoded = Vec::with_capacity_in(len, alloc);
+    encoded.resize(len, 0);
+
+    let written = data.encode_slice(base, &mut encoded)
+        .expect("buffer is sized to the maximum encoded length");
+
+    encoded.truncate(written);
+    encoded
+}
+
+/// Decode the string into a vector in `alloc`, such as an arena.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use multibase::{Base, decode_in};
+///
+/// assert_eq!(decode_in("zCn8eVZg", Global).unwrap(), (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_in<T: AsRef<str>, A: Allocator>(data: T, alloc: A) -> Result<(Base, Vec<u8, A>)> {
+    let data = data.as_ref();
+    let (base, _) = split(data)?;
+    let len = base.max_decoded_len(data.len());
+    let mut decoded = Vec::with_capacity_in(len, alloc);
+    decoded.resize(len, 0);
+
+    let (base, written) = data.decode_slice(&mut decoded)?;
+    decoded.truncate(written);
+
+    Ok((base, decoded))
+}
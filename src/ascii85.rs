@@ -0,0 +1,181 @@
+//! [Ascii85](https://en.wikipedia.org/wiki/Ascii85), the btoa/Adobe flavour
+//! of base85 used by PostScript and PDF.
+//!
+//! Ascii85 has no multibase code, so the output carries no prefix.
+
+#[cfg(feature = "encode")]
+use alloc::string::String;
+#[cfg(feature = "decode")]
+use alloc::vec::Vec;
+
+use codec::Codec;
+use Result;
+#[cfg(feature = "decode")]
+use {DecodeError, Error};
+
+const OFFSET: u8 = b'!';
+const ZERO_GROUP: u8 = b'z';
+
+#[cfg(feature = "encode")]
+fn encode_with(data: &[u8], zero_groups: bool) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(4) * 5);
+
+    for block in data.chunks(4) {
+        let mut bytes = [0u8; 4];
+        bytes[..block.len()].copy_from_slice(block);
+
+        let mut value = u32::from_be_bytes(bytes);
+
+        if zero_groups && value == 0 && block.len() == 4 {
+            encoded.push(ZERO_GROUP as char);
+            continue;
+        }
+
+        let mut chars = [0u8; 5];
+
+        for c in chars.iter_mut().rev() {
+            *c = OFFSET + (value % 85) as u8;
+            value /= 85;
+        }
+
+        for &c in &chars[..block.len() + 1] {
+            encoded.push(c as char);
+        }
+    }
+
+    encoded
+}
+
+/// Encode the data as Ascii85, without the `z` shorthand for zero groups.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::ascii85;
+///
+/// assert_eq!(ascii85::encode(b"Man "), "9jqo^");
+/// assert_eq!(ascii85::encode([0; 4]), "!!!!!");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
+    encode_with(data.as_ref(), false)
+}
+
+/// Encode the data as Ascii85, writing groups of four zero bytes as `z`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::ascii85;
+///
+/// assert_eq!(ascii85::encode_compact([0; 4]), "z");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_compact<T: AsRef<[u8]>>(data: T) -> String {
+    encode_with(data.as_ref(), true)
+}
+
+/// Decode an Ascii85 string.
+///
+/// The `z` shorthand is always accepted, whitespace is ignored, and the
+/// `<~`/`~>` delimiters emitted by Adobe tools are stripped if present.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::ascii85;
+///
+/// assert_eq!(ascii85::decode("<~9jqo^~>").unwrap(), b"Man ".to_vec());
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+    let input = data.as_ref();
+    let mut data = input.trim();
+    let mut start = input.len() - input.trim_start().len();
+
+    if data.starts_with("<~") {
+        data = &data[2..];
+        start += 2;
+    }
+
+    if data.ends_with("~>") {
+        data = &data[..data.len() - 2];
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() / 5 * 4 + 4);
+    let mut group = [0u8; 5];
+    let mut len = 0;
+    let mut group_start = 0;
+
+    for (i, c) in data.bytes().enumerate().filter(|&(_, c)| !c.is_ascii_whitespace()) {
+        if c == ZERO_GROUP && len == 0 {
+            decoded.extend_from_slice(&[0; 4]);
+            continue;
+        }
+
+        if !(OFFSET..OFFSET + 85).contains(&c) {
+            return Err(Error::invalid_character(input.as_bytes(), start + i));
+        }
+
+        if len == 0 {
+            group_start = start + i;
+        }
+
+        group[len] = c - OFFSET;
+        len += 1;
+
+        if len == 5 {
+            decoded.extend_from_slice(&decode_group(&group, group_start)?);
+            len = 0;
+        }
+    }
+
+    match len {
+        0 => {}
+        1 => return Err(Error::InvalidLength),
+        _ => {
+            for digit in group[len..].iter_mut() {
+                *digit = 84;
+            }
+
+            decoded.extend_from_slice(&decode_group(&group, group_start)?[..len - 1]);
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(feature = "decode")]
+fn decode_group(group: &[u8; 5], position: usize) -> Result<[u8; 4]> {
+    let value = group.iter().fold(0u64, |value, &digit| value * 85 + u64::from(digit));
+
+    if value > u64::from(u32::MAX) {
+        return Err(DecodeError::Overflow { position }.into());
+    }
+
+    Ok((value as u32).to_be_bytes())
+}
+
+/// The Ascii85 encoding as a runtime `Codec`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Ascii85;
+
+impl Codec for Ascii85 {
+    fn name(&self) -> &str {
+        "ascii85"
+    }
+
+    fn code(&self) -> Option<char> {
+        None
+    }
+
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String> {
+        Ok(encode(data))
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+        decode(data)
+    }
+}
@@ -1,22 +1,61 @@
-use {Error, Result};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+use std::str;
+
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use alloc::string::String;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+use alloc::vec::Vec;
+
+#[cfg(feature = "decode")]
+use lookup::{self, Lookup};
+use {bignum, rfc4648, Error, Result};
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use Encodable;
+
+/// Status of a base in the multibase spec table.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Status {
+    /// Implementations are expected to support it.
+    Default,
+    /// On track to become a default base.
+    Candidate,
+    /// Still under discussion.
+    Draft,
+    /// Not part of the spec yet and subject to change.
+    Experimental,
+}
 
 macro_rules! build_base_enum {
-    {$( $val:expr => $var:ident: $alph:expr, )*} => {
+    {$( $(#[$attr:meta])* $val:literal => $var:ident, $module:ident: $name:literal, $alph:literal ($status:ident, $padded:literal), )*} => {
         #[derive(PartialEq, Eq, Clone, Copy, Debug)]
         pub enum Base {
-            $( $var, )*
+            $( $(#[$attr])* $var, )*
         }
 
         use Base::*;
 
         impl Base {
-            /// Get the base code.
+            /// Every supported base, in multibase table order.
+            pub const ALL: &'static [Base] = &[$( $var, )*];
+
+            /// Get the base code. Codes are Unicode scalar values, not bytes,
+            /// so they may take more than one byte once encoded.
             pub fn code(&self) -> char {
                 match *self {
                     $( $var => $val, )*
                 }
             }
 
+            /// Get the canonical multibase name, such as `base32hexpadupper`.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    $( $var => $name, )*
+                }
+            }
+
             /// Get the matching alphabet.
             pub fn alphabet(&self) -> &[u8] {
                 match *self {
@@ -24,10 +63,70 @@ macro_rules! build_base_enum {
                 }
             }
 
+            /// Map from characters to their values in the alphabet, `0xff`
+            /// for characters not in it.
+            #[cfg(feature = "decode")]
+            pub(crate) fn lookup(&self) -> Lookup {
+                match *self {
+                    $( $var => {
+                        const TABLE: lookup::Table = lookup::table($alph);
+                        Lookup::new(&TABLE, lookup::swaps_case($alph))
+                    } )*
+                }
+            }
+
+            /// Get the status of the base in the multibase spec table.
+            pub fn status(&self) -> Status {
+                match *self {
+                    $( $var => Status::$status, )*
+                }
+            }
+
+            /// Whether the encoded output is padded to whole blocks.
+            pub fn is_padded(&self) -> bool {
+                match *self {
+                    $( $var => $padded, )*
+                }
+            }
+
             /// Convert a code to a base.
+            ///
+            /// Takes the first `char` of a multibase string, which may span
+            /// several bytes.
+            #[inline]
             pub fn from_code(code: char) -> Result<Base> {
-                match code {
-                    $( $val => Ok($var), )*
+                // Nearly all codes are ASCII, so those are looked up in a
+                // table built at compile time.
+                const ASCII: [Option<Base>; 128] = {
+                    let mut table = [None; 128];
+                    $( if ($val as u32) < 128 { table[$val as usize] = Some($var); } )*
+                    table
+                };
+
+                match ASCII.get(code as usize) {
+                    Some(&base) => base.ok_or(Error::UnkownBase),
+                    None => match code {
+                        $( $val => Ok($var), )*
+                        _ => Err(Error::UnkownBase),
+                    },
+                }
+            }
+        }
+
+        impl fmt::Display for Base {
+            /// Write the canonical multibase name, such as `base58btc`.
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+
+        impl FromStr for Base {
+            type Err = Error;
+
+            /// Parse a canonical multibase name, such as `base58btc`.
+            fn from_str(name: &str) -> Result<Base> {
+                match name {
+                    $( $name => Ok($var), )*
                     _ => Err(Error::UnkownBase),
                 }
             }
@@ -35,57 +134,239 @@ macro_rules! build_base_enum {
     }
 }
 
-build_base_enum! {
-    // unary tends to be 11111
-    // '1' => Base1: unimplemented!(),
+for_each_base!(build_base_enum);
+
+impl Base {
+    /// Whether the alphabet relies on letter case to tell characters apart.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert!(Base::Base58btc.is_case_sensitive());
+    /// assert!(!Base::Base32.is_case_sensitive());
+    /// ```
+    pub fn is_case_sensitive(&self) -> bool {
+        let alphabet = self.alphabet();
+
+        alphabet.iter().any(u8::is_ascii_lowercase) && alphabet.iter().any(u8::is_ascii_uppercase)
+    }
+
+    /// Number of bits each character carries, `log2` of the alphabet size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base64.bits_per_char(), 6.0);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn bits_per_char(&self) -> f64 {
+        (self.alphabet().len() as f64).log2()
+    }
 
-    // binary has 1 and 0
-    '0' => Base2: b"01",
+    /// Number of bytes of data each character carries, between 0 and 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base16.efficiency(), 0.5);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn efficiency(&self) -> f64 {
+        self.bits_per_char() / 8.0
+    }
 
-    // highest char in octal
-    '7' => Base8: b"01234567",
+    /// Maximum length of the encoding of `len` bytes, prefix included.
+    /// Exact for the bitwise bases, a tight upper bound for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base64pad.max_encoded_len(5), 9);
+    /// assert_eq!(Base::Base58btc.max_encoded_len(32), 45);
+    /// ```
+    pub fn max_encoded_len(&self, len: usize) -> usize {
+        self.code().len_utf8() + self.max_raw_encoded_len(len)
+    }
 
-    // highest char in decimal
-    '9' => Base10: b"0123456789",
+    /// Maximum length of the data decoded from a `len`-byte string, prefix
+    /// included. Exact for the bitwise bases, an upper bound for the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base64pad.max_decoded_len(9), 6);
+    /// ```
+    pub fn max_decoded_len(&self, len: usize) -> usize {
+        self.max_raw_decoded_len(len.saturating_sub(self.code().len_utf8()))
+    }
 
-    // highest char in hex
-    'f' => Base16: b"0123456789abcdef",
-    'F' => Base16Upper: b"0123456789ABCDEF",
+    /// Encode with this base, prefix included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base58btc.encode(b"hello"), "zCn8eVZg");
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "encode"))]
+    #[inline]
+    pub fn encode<T: AsRef<[u8]>>(&self, data: T) -> String {
+        data.as_ref().encode(*self)
+    }
 
-    // rfc4648 no padding - highest char
-    'v' => Base32hex: b"0123456789abcdefghijklmnopqrstuv",
-    'V' => Base32hexUpper: b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+    /// Encode with this base, leaving out the prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base58btc.encode_raw(b"hello"), "Cn8eVZg");
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "encode"))]
+    pub fn encode_raw<T: AsRef<[u8]>>(&self, data: T) -> String {
+        let data = data.as_ref();
+        let mut encoded = vec![0; self.max_raw_encoded_len(data.len())];
 
-    // rfc4648 with padding
-    // 't' => Base32hexpad: unimplemented!(),
-    // 'T' => Base32hexpadUpper: unimplemented!(),
+        let written = self.encode_raw_slice(data, &mut encoded)
+            .expect("buffer is sized to the maximum encoded length");
+        encoded.truncate(written);
 
-    // rfc4648 no padding
-    'b' => Base32: b"abcdefghijklmnopqrstuvwxyz234567",
-    'B' => Base32Upper: b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+        // Alphabets are ASCII.
+        unsafe { String::from_utf8_unchecked(encoded) }
+    }
 
-    // rfc4648 with padding
-    // 'c' => Base32pad: unimplemented!(),
-    // 'C' => Base32padUpper: unimplemented!(),
+    /// Decode a string encoded with this base that has no prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert_eq!(Base::Base58btc.decode_raw("Cn8eVZg").unwrap(), b"hello");
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "decode"))]
+    pub fn decode_raw<T: AsRef<str>>(&self, data: T) -> Result<Vec<u8>> {
+        let data = data.as_ref();
+        let mut decoded = vec![0; self.max_raw_decoded_len(data.len())];
 
-    // z-base-32 - used by Tahoe-LAFS - highest letter
-    'h' => Base32z: b"ybndrfg8ejkmcpqxot1uwisza345h769",
+        let written = self.decode_raw_slice(data, &mut decoded)?;
+        decoded.truncate(written);
 
-    // highest letter
-    'Z' => Base58flickr: b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+        Ok(decoded)
+    }
 
-    // highest letter
-    'z' => Base58btc: b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    /// Whether this base is encoded bit by bit, as described in RFC 4648,
+    /// rather than as one big number. Holds for the power-of-two alphabets:
+    /// base2, base8, base16, base32 and base64 in all their variants.
+    ///
+    /// Bitwise bases encode and decode a block at a time, so the streaming
+    /// APIs handle them in constant memory. The others need the whole input
+    /// before they can produce anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use multibase::Base;
+    ///
+    /// assert!(Base::Base32.is_bitwise());
+    /// assert!(!Base::Base58btc.is_bitwise());
+    /// ```
+    #[inline]
+    pub fn is_bitwise(&self) -> bool {
+        self.alphabet().len().is_power_of_two()
+    }
 
-    // rfc4648 no padding
-    'm' => Base64: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+    #[inline]
+    pub(crate) fn max_raw_encoded_len(&self, len: usize) -> usize {
+        if self.is_bitwise() {
+            rfc4648::encoded_len(self.alphabet(), self.is_padded(), len)
+        } else {
+            bignum::max_encoded_len(self.alphabet(), len)
+        }
+    }
 
-    // rfc4648 with padding - MIME encoding
-    // 'M' => Base64pad: unimplemented!(),
+    #[inline]
+    pub(crate) fn max_raw_decoded_len(&self, len: usize) -> usize {
+        if self.is_bitwise() {
+            rfc4648::max_decoded_len(self.alphabet(), len)
+        } else {
+            bignum::max_decoded_len(len)
+        }
+    }
 
-    // rfc4648 no padding
-    'u' => Base64url: b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+    #[cfg(feature = "encode")]
+    #[inline]
+    pub(crate) fn encode_raw_slice(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        if self.is_bitwise() {
+            rfc4648::encode(self.alphabet(), self.is_padded(), data, out)
+        } else {
+            bignum::encode(self.alphabet(), data, out)
+        }
+    }
 
-    // rfc4648 with padding
-    // 'U' => Base64urlpad: unimplemented!(),
+    #[cfg(feature = "decode")]
+    #[inline]
+    pub(crate) fn decode_raw_slice(&self, input: &str, out: &mut [u8]) -> Result<usize> {
+        if self.is_bitwise() {
+            rfc4648::decode(self.alphabet(), self.lookup(), self.is_padded(), input, out)
+        } else {
+            bignum::decode(self.alphabet(), self.lookup(), input, out)
+        }
+    }
+
+    /// Decode `buf[start..]` into the front of `buf`, returning the number
+    /// of bytes written. Only bitwise bases can be decoded in place; the
+    /// others go through a temporary buffer.
+    #[cfg(all(feature = "alloc", feature = "decode"))]
+    pub(crate) fn decode_raw_in_place(&self, buf: &mut [u8], start: usize) -> Result<usize> {
+        if self.is_bitwise() {
+            return rfc4648::decode_in_place(self.alphabet(), self.lookup(), self.is_padded(), buf, start);
+        }
+
+        let input = str::from_utf8(&buf[start..]).map_err(|err| Error::from(err).offset(start))?;
+        let mut decoded = vec![0; self.max_raw_decoded_len(input.len())];
+
+        let written = self.decode_raw_slice(input, &mut decoded).map_err(|err| err.offset(start))?;
+        buf[..written].copy_from_slice(&decoded[..written]);
+
+        Ok(written)
+    }
+
+    #[cfg(feature = "decode")]
+    #[inline]
+    pub(crate) fn validate_raw(&self, input: &str) -> Result<()> {
+        if self.is_bitwise() {
+            rfc4648::validate(self.alphabet(), self.lookup(), self.is_padded(), input)
+        } else {
+            bignum::validate(self.lookup(), input)
+        }
+    }
+}
+
+impl TryFrom<char> for Base {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(code: char) -> Result<Base> {
+        Base::from_code(code)
+    }
+}
+
+impl From<Base> for char {
+    #[inline]
+    fn from(base: Base) -> char {
+        base.code()
+    }
 }
@@ -0,0 +1,138 @@
+//! [basE91](http://base91.sourceforge.net/), a dense text encoding that
+//! maps 13 or 14 bits onto every pair of characters.
+//!
+//! basE91 has no multibase code, so the output carries no prefix.
+
+#[cfg(feature = "encode")]
+use alloc::string::String;
+#[cfg(feature = "decode")]
+use alloc::vec::Vec;
+
+use codec::Codec;
+use Result;
+#[cfg(feature = "decode")]
+use Error;
+
+const ALPHABET: &[u8; 91] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+
+/// Encode the data as basE91.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::base91;
+///
+/// assert_eq!(base91::encode(b"test"), "fPNKd");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
+    let data = data.as_ref();
+    let mut encoded = String::with_capacity(data.len() * 16 / 13 + 2);
+
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+
+    for &byte in data {
+        buffer |= u32::from(byte) << buffered;
+        buffered += 8;
+
+        if buffered > 13 {
+            let mut value = buffer & 8191;
+
+            if value > 88 {
+                buffer >>= 13;
+                buffered -= 13;
+            } else {
+                value = buffer & 16383;
+                buffer >>= 14;
+                buffered -= 14;
+            }
+
+            encoded.push(ALPHABET[(value % 91) as usize] as char);
+            encoded.push(ALPHABET[(value / 91) as usize] as char);
+        }
+    }
+
+    if buffered > 0 {
+        encoded.push(ALPHABET[(buffer % 91) as usize] as char);
+
+        if buffered > 7 || buffer > 90 {
+            encoded.push(ALPHABET[(buffer / 91) as usize] as char);
+        }
+    }
+
+    encoded
+}
+
+/// Decode a basE91 string.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::base91;
+///
+/// assert_eq!(base91::decode("fPNKd").unwrap(), b"test".to_vec());
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+    let data = data.as_ref().as_bytes();
+    let mut decoded = Vec::with_capacity(data.len() * 14 / 16 + 1);
+
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+    let mut pending = None;
+
+    for (i, &c) in data.iter().enumerate() {
+        let digit = ALPHABET.iter().position(|&a| a == c)
+            .ok_or_else(|| Error::invalid_character(data, i))? as u32;
+
+        let low = match pending.take() {
+            Some(low) => low,
+            None => {
+                pending = Some(digit);
+                continue;
+            }
+        };
+
+        let value = low + digit * 91;
+
+        buffer |= value << buffered;
+        buffered += if value & 8191 > 88 { 13 } else { 14 };
+
+        while buffered > 7 {
+            decoded.push(buffer as u8);
+            buffer >>= 8;
+            buffered -= 8;
+        }
+    }
+
+    if let Some(low) = pending {
+        decoded.push((buffer | low << buffered) as u8);
+    }
+
+    Ok(decoded)
+}
+
+/// The basE91 encoding as a runtime `Codec`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Base91;
+
+impl Codec for Base91 {
+    fn name(&self) -> &str {
+        "base91"
+    }
+
+    fn code(&self) -> Option<char> {
+        None
+    }
+
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String> {
+        Ok(encode(data))
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+        decode(data)
+    }
+}
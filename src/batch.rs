@@ -0,0 +1,115 @@
+#[cfg(feature = "encode")]
+use alloc::borrow::ToOwned;
+#[cfg(feature = "encode")]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use Context;
+#[cfg(feature = "encode")]
+use Base;
+#[cfg(feature = "decode")]
+use {Decodable, Decoded, Result};
+
+/// Encode every item with the given base.
+///
+/// Items are encoded through one `Context` that is reused across the
+/// batch, so each output is allocated once at its exact length.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_many};
+///
+/// assert_eq!(encode_many(Base::Base58btc, &[b"hello", b"world"]),
+///            ["zCn8eVZg", "zEUYUqQf"]);
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_many<T: AsRef<[u8]>>(base: Base, items: &[T]) -> Vec<String> {
+    let mut context = Context::new();
+
+    items.iter()
+        .map(|item| context.encode(base, item.as_ref()).to_owned())
+        .collect()
+}
+
+/// Decode every item, stopping at the first one that fails.
+///
+/// Items are decoded through one `Context` that is reused across the
+/// batch, so each output is allocated once at its exact length.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_many};
+///
+/// let decoded = decode_many(&["zCn8eVZg", "f776f726c64"]).unwrap();
+///
+/// assert_eq!(decoded[0], (Base::Base58btc, b"hello".to_vec()));
+/// assert_eq!(decoded[1], (Base::Base16, b"world".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_many<T: AsRef<str>>(items: &[T]) -> Result<Vec<Decoded>> {
+    let mut context = Context::new();
+
+    items.iter()
+        .map(|item| {
+            let (base, data) = context.decode(item)?;
+
+            Ok(Decoded { base, data: data.to_vec() })
+        })
+        .collect()
+}
+
+/// Decode a token found at `token` within `text`, with error positions
+/// relative to `text`.
+#[cfg(feature = "decode")]
+fn decode_token(text: &str, token: &str) -> Result<Decoded> {
+    let start = token.as_ptr() as usize - text.as_ptr() as usize;
+
+    token.decode().map_err(|err| err.offset(start))
+}
+
+/// Decode every line of the text as a multibase string, for log files and
+/// other line-oriented input. Whitespace around each line is trimmed and
+/// blank lines are skipped. Error positions are byte offsets into the whole
+/// text.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_lines};
+///
+/// let decoded: Vec<_> = decode_lines("zCn8eVZg\r\n\n  f776f726c64\n")
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(decoded, [(Base::Base58btc, b"hello".to_vec()),
+///                      (Base::Base16, b"world".to_vec())]);
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_lines(text: &str) -> impl Iterator<Item = Result<Decoded>> + '_ {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(move |line| decode_token(text, line))
+}
+
+/// Decode every whitespace-separated token of the text as a multibase
+/// string. Error positions are byte offsets into the whole text.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, DecodeError, Error, decode_delimited};
+///
+/// let decoded: Vec<_> = decode_delimited("zCn8eVZg f776f726c64\tzCn8eVZ0").collect();
+///
+/// assert_eq!(decoded[0].as_ref().unwrap().data, b"hello");
+/// assert_eq!(decoded[1].as_ref().unwrap().data, b"world");
+/// assert_eq!(decoded[2], Err(Error::InvalidBaseString(
+///     DecodeError::InvalidCharacter { character: '0', position: 28 })));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_delimited(text: &str) -> impl Iterator<Item = Result<Decoded>> + '_ {
+    text.split_whitespace().map(move |token| decode_token(text, token))
+}
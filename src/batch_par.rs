@@ -0,0 +1,55 @@
+use rayon::prelude::*;
+
+use Context;
+#[cfg(feature = "encode")]
+use Base;
+#[cfg(feature = "decode")]
+use {Decoded, Result};
+
+/// Encode every item with the given base, spreading the items across the
+/// rayon thread pool. The output is in the same order as the items.
+///
+/// Each thread encodes through its own `Context`, so the scratch buffers
+/// are reused the same way as in [`encode_many`](fn.encode_many.html).
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_many_par};
+///
+/// assert_eq!(encode_many_par(Base::Base58btc, &[b"hello", b"world"]),
+///            ["zCn8eVZg", "zEUYUqQf"]);
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_many_par<T: AsRef<[u8]> + Sync>(base: Base, items: &[T]) -> Vec<String> {
+    items.par_iter()
+        .map_init(Context::new, |context, item| context.encode(base, item.as_ref()).to_owned())
+        .collect()
+}
+
+/// Decode every item, spreading the items across the rayon thread pool.
+/// The output is in the same order as the items.
+///
+/// If several items fail, which of their errors is returned is
+/// unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_many_par};
+///
+/// let decoded = decode_many_par(&["zCn8eVZg", "f776f726c64"]).unwrap();
+///
+/// assert_eq!(decoded[0], (Base::Base58btc, b"hello".to_vec()));
+/// assert_eq!(decoded[1], (Base::Base16, b"world".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_many_par<T: AsRef<str> + Sync>(items: &[T]) -> Result<Vec<Decoded>> {
+    items.par_iter()
+        .map_init(Context::new, |context, item| {
+            let (base, data) = context.decode(item)?;
+
+            Ok(Decoded { base, data: data.to_vec() })
+        })
+        .collect()
+}
@@ -0,0 +1,358 @@
+//! Radix conversion for alphabets that don't map onto whole bits, treating
+//! the input as one big-endian number. Leading zero bytes are preserved as
+//! leading zero digits, the same way Bitcoin's base58 does.
+
+#[cfg(all(feature = "rayon", feature = "encode"))]
+use bignum_par;
+#[cfg(feature = "decode")]
+use lookup::{Lookup, INVALID};
+use {Error, Result};
+
+/// Fractional bits kept by `log2`.
+const LOG2_PRECISION: u32 = 16;
+
+/// Bits of input folded into the number per pass over the digits. Carries
+/// stay below `radix << CHUNK_BITS`, which fits a `u64` for any radix up to
+/// 256.
+const CHUNK_BITS: u32 = 48;
+
+/// Inputs this short, leading zeros excluded, are converted as a `u128`.
+#[cfg(feature = "encode")]
+const SHORT_LEN: usize = 16;
+
+/// Lower bound on `log2(radix)` as a fixed-point number, computed by
+/// repeated squaring. Every step rounds down, so the result never
+/// overestimates the bits a digit carries.
+fn log2(radix: u32) -> u64 {
+    let int = 31 - radix.leading_zeros();
+    let mut log = u64::from(int) << LOG2_PRECISION;
+
+    // radix / 2^int, in [1, 2) with 62 fractional bits.
+    let mut x = u128::from(radix) << (62 - int);
+
+    for bit in (0..LOG2_PRECISION).rev() {
+        x = (x * x) >> 62;
+
+        if x >= 2 << 62 {
+            x >>= 1;
+            log |= 1 << bit;
+        }
+    }
+
+    log
+}
+
+/// Digits per limb, and the limb radix: the largest power of `radix` that
+/// fits a `u32` limb.
+#[cfg(feature = "encode")]
+pub fn limb_scale(radix: u64) -> (usize, u64) {
+    let mut digits = 0;
+    let mut scale = 1;
+
+    while scale * radix <= 1 << 32 {
+        scale *= radix;
+        digits += 1;
+    }
+
+    (digits, scale)
+}
+
+/// Upper bound on the encoding of `len` bytes.
+pub fn max_encoded_len(alphabet: &[u8], len: usize) -> usize {
+    let bits = (len as u64 * 8) << LOG2_PRECISION;
+
+    bits.div_ceil(log2(alphabet.len() as u32)) as usize
+}
+
+/// Encode `data` with `alphabet` into `out`, returning the number of bytes
+/// written.
+#[cfg(feature = "encode")]
+pub fn encode(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> Result<usize> {
+    // Scratch space is taken from the end of `out`, so keep it within the
+    // longest possible encoding: bytes between the actual and the longest
+    // encoding are clobbered, but the caller's bytes past those are not.
+    let len = out.len().min(max_encoded_len(alphabet, data.len()));
+    let out = &mut out[..len];
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+
+    #[cfg(feature = "rayon")]
+    {
+        if data.len() - zeros >= bignum_par::PARALLEL_LEN {
+            return bignum_par::encode(alphabet, &data[zeros..], zeros, out);
+        }
+    }
+
+    if data.len() - zeros <= SHORT_LEN {
+        return encode_short(alphabet, &data[zeros..], zeros, out);
+    }
+
+    match encode_limbs(alphabet, &data[zeros..], zeros, out) {
+        Err(Error::BufferTooSmall) => encode_digits(alphabet, data, zeros, out),
+        result => result,
+    }
+}
+
+/// Encode a number short enough to fit a `u128`, without touching `out`
+/// until the digits are known.
+#[cfg(feature = "encode")]
+fn encode_short(alphabet: &[u8], data: &[u8], zeros: usize, out: &mut [u8]) -> Result<usize> {
+    let radix = alphabet.len() as u64;
+    let (digits, scale) = limb_scale(radix);
+
+    // 128 bits take at most 81 digits, in base 3.
+    let mut buffer = [0; 128];
+    let mut start = buffer.len();
+    let mut value = data.iter().fold(0u128, |value, &byte| (value << 8) | u128::from(byte));
+
+    // Split off whole limbs with the wide division, then the digits of each
+    // limb with the narrow one.
+    while value >= u128::from(scale) {
+        let mut limb = (value % u128::from(scale)) as u64;
+        value /= u128::from(scale);
+
+        for _ in 0..digits {
+            start -= 1;
+            buffer[start] = alphabet[(limb % radix) as usize];
+            limb /= radix;
+        }
+    }
+
+    let mut limb = value as u64;
+
+    while limb > 0 {
+        start -= 1;
+        buffer[start] = alphabet[(limb % radix) as usize];
+        limb /= radix;
+    }
+
+    let len = zeros + buffer.len() - start;
+    let out = out.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+    for c in out[..zeros].iter_mut() {
+        *c = alphabet[0];
+    }
+
+    out[zeros..].copy_from_slice(&buffer[start..]);
+
+    Ok(len)
+}
+
+/// Read the limb stored `index` limbs from the end of `out`.
+#[cfg(feature = "encode")]
+#[inline]
+fn limb(out: &[u8], index: usize) -> u32 {
+    let at = out.len() - 4 * (index + 1);
+    u32::from_ne_bytes([out[at], out[at + 1], out[at + 2], out[at + 3]])
+}
+
+#[cfg(feature = "encode")]
+#[inline]
+fn set_limb(out: &mut [u8], index: usize, limb: u32) {
+    let at = out.len() - 4 * (index + 1);
+    out[at..at + 4].copy_from_slice(&limb.to_ne_bytes());
+}
+
+/// Encode the number after the leading zeros a block at a time: the number
+/// is built in `u32` limbs of as many digits as fit, 4 bytes of input per
+/// pass, so there is one division per limb instead of one per digit.
+///
+/// The limbs are kept at the end of `out`, most significant first, and the
+/// digits written from the front catch up with them limb by limb without
+/// overtaking. Fails with `Error::BufferTooSmall` if `out` can't hold the
+/// limbs, which may happen for tiny outputs even if the digits would fit.
+#[cfg(feature = "encode")]
+fn encode_limbs(alphabet: &[u8], data: &[u8], zeros: usize, out: &mut [u8]) -> Result<usize> {
+    let radix = alphabet.len() as u32;
+
+    // At least 4 digits per limb for any radix up to 256, which keeps the
+    // digits from overtaking the limbs.
+    let (digits, scale) = limb_scale(u64::from(radix));
+
+    let mut limbs = 0;
+
+    for chunk in data.chunks(4) {
+        let shift = 8 * chunk.len() as u32;
+        let mut carry = chunk.iter().fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+
+        for i in 0..limbs {
+            carry += u64::from(limb(out, i)) << shift;
+            set_limb(out, i, (carry % scale) as u32);
+            carry /= scale;
+        }
+
+        while carry > 0 {
+            if 4 * (limbs + 1) > out.len() {
+                return Err(Error::BufferTooSmall);
+            }
+
+            set_limb(out, limbs, (carry % scale) as u32);
+            carry /= scale;
+            limbs += 1;
+        }
+    }
+
+    // The top limb has no leading zero digits, the others are full.
+    let top = if limbs > 0 { limb(out, limbs - 1) } else { 0 };
+    let mut top_digits = 0;
+
+    while u64::from(top) >= u64::from(radix).pow(top_digits) {
+        top_digits += 1;
+    }
+
+    let len = zeros + top_digits as usize + limbs.saturating_sub(1) * digits;
+
+    if len > out.len() {
+        return Err(Error::BufferTooSmall);
+    }
+
+    let mut written = zeros;
+
+    for i in (0..limbs).rev() {
+        let mut value = limb(out, i);
+        let count = if i == limbs - 1 { top_digits as usize } else { digits };
+
+        for at in (written..written + count).rev() {
+            out[at] = alphabet[(value % radix) as usize];
+            value /= radix;
+        }
+
+        written += count;
+    }
+
+    for c in out[..zeros].iter_mut() {
+        *c = alphabet[0];
+    }
+
+    Ok(len)
+}
+
+/// Encode a digit at a time, for outputs too small to hold the limbs.
+///
+/// The digits are kept at the end of `out`, most significant first, so
+/// they come out in order; once complete they are moved behind the leading
+/// zeros and mapped onto the alphabet in the same pass.
+#[cfg(feature = "encode")]
+fn encode_digits(alphabet: &[u8], data: &[u8], zeros: usize, out: &mut [u8]) -> Result<usize> {
+    let radix = alphabet.len() as u64;
+    let end = out.len();
+    let mut len = 0;
+
+    // Each pass multiplies the number so far by 2^48 and adds the next 6
+    // bytes.
+    for chunk in data[zeros..].chunks(CHUNK_BITS as usize / 8) {
+        let shift = 8 * chunk.len() as u32;
+        let mut carry = chunk.iter().fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+
+        for digit in out[end - len..].iter_mut().rev() {
+            carry += u64::from(*digit) << shift;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+
+        while carry > 0 {
+            if len == end {
+                return Err(Error::BufferTooSmall);
+            }
+
+            len += 1;
+            out[end - len] = (carry % radix) as u8;
+            carry /= radix;
+        }
+    }
+
+    if zeros + len > end {
+        return Err(Error::BufferTooSmall);
+    }
+
+    // Every digit moves forward or stays put, so none is overwritten
+    // before it has been read.
+    for i in 0..len {
+        out[zeros + i] = alphabet[out[end - len + i] as usize];
+    }
+
+    for c in out[..zeros].iter_mut() {
+        *c = alphabet[0];
+    }
+
+    Ok(zeros + len)
+}
+
+/// Upper bound on the decoding of `len` characters.
+pub fn max_decoded_len(len: usize) -> usize {
+    // A digit never carries more than a byte, and each leading zero digit
+    // maps onto exactly one zero byte.
+    len
+}
+
+/// Decode `input` with `alphabet` into `out`, returning the number of bytes
+/// written.
+#[cfg(feature = "decode")]
+pub fn decode(alphabet: &[u8], lookup: Lookup, input: &str, out: &mut [u8]) -> Result<usize> {
+    let radix = alphabet.len() as u64;
+    let input = input.as_bytes();
+
+    // As many digits as fit in 48 bits are folded in per pass.
+    let mut digits = 0;
+    let mut scale = 1;
+
+    while scale * radix <= 1 << CHUNK_BITS {
+        scale *= radix;
+        digits += 1;
+    }
+
+    let zeros = input.iter().take_while(|&&c| c == alphabet[0]).count();
+
+    // Bytes are accumulated least significant first, then reversed.
+    let mut len = 0;
+    let mut start = zeros;
+
+    while start < input.len() {
+        let end = input.len().min(start + digits);
+        let mut carry = 0;
+        let mut scale = 1;
+
+        for i in start..end {
+            let value = lookup.get(input[i]);
+
+            if value == INVALID {
+                return Err(Error::invalid_character(input, i));
+            }
+
+            carry = carry * radix + u64::from(value);
+            scale *= radix;
+        }
+
+        start = end;
+
+        for byte in out[..len].iter_mut() {
+            carry += u64::from(*byte) * scale;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            *out.get_mut(len).ok_or(Error::BufferTooSmall)? = carry as u8;
+            carry >>= 8;
+            len += 1;
+        }
+    }
+
+    for _ in 0..zeros {
+        *out.get_mut(len).ok_or(Error::BufferTooSmall)? = 0;
+        len += 1;
+    }
+
+    out[..len].reverse();
+
+    Ok(len)
+}
+
+/// Check that every character of `input` is in `alphabet`, without
+/// decoding. Any such string is a valid number.
+#[cfg(feature = "decode")]
+pub fn validate(lookup: Lookup, input: &str) -> Result<()> {
+    match input.bytes().position(|c| lookup.get(c) == INVALID) {
+        Some(position) => Err(Error::invalid_character(input.as_bytes(), position)),
+        None => Ok(()),
+    }
+}
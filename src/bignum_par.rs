@@ -0,0 +1,205 @@
+//! Divide-and-conquer radix conversion for huge inputs, spread across the
+//! rayon thread pool.
+//!
+//! The input is split into a high and a low half, both halves are
+//! converted on their own, and the results are recombined as
+//! `high * 256^len(low) + low`. The numbers are kept in limbs of as many
+//! digits as fit a `u32`, least significant first, so the recombination is
+//! done entirely in the target radix and the digits fall out of the limbs
+//! at the end.
+
+use rayon::prelude::*;
+
+use {bignum, Error, Result};
+
+/// Inputs at least this long, leading zeros excluded, are encoded in
+/// parallel.
+pub const PARALLEL_LEN: usize = 4 * 1024;
+
+/// Pieces this short are converted directly.
+const LEAF_LEN: usize = 1024;
+
+/// Output columns computed per task when multiplying.
+const COLUMNS_PER_TASK: usize = 1024;
+
+/// Limbs of the number so far, least significant first, and their radix.
+type Limbs = Vec<u64>;
+
+/// Encode the number after the leading zeros in `data`, writing the digits
+/// behind `zeros` leading zero digits.
+pub fn encode(alphabet: &[u8], data: &[u8], zeros: usize, out: &mut [u8]) -> Result<usize> {
+    let radix = alphabet.len() as u64;
+
+    let (digits, scale) = bignum::limb_scale(radix);
+
+    // 256^(LEAF_LEN << level) for every level of the split.
+    let mut powers = vec![to_limbs(&one_followed_by(LEAF_LEN), scale)];
+
+    while LEAF_LEN << powers.len() < data.len() {
+        let last = powers.last().expect("powers start with one entry");
+        let square = multiply(last, last, scale);
+        powers.push(square);
+    }
+
+    let limbs = convert(data, &powers, scale);
+
+    // The top limb has no leading zero digits, the others are full.
+    let top = limbs.last().cloned().unwrap_or(0);
+    let mut top_digits = 0;
+
+    while top >= radix.pow(top_digits) {
+        top_digits += 1;
+    }
+
+    let len = zeros + top_digits as usize + limbs.len().saturating_sub(1) * digits;
+
+    if len > out.len() {
+        return Err(Error::BufferTooSmall);
+    }
+
+    let mut written = len;
+
+    for (i, &limb) in limbs.iter().enumerate() {
+        let mut value = limb;
+        let count = if i == limbs.len() - 1 { top_digits as usize } else { digits };
+
+        for c in out[written - count..written].iter_mut().rev() {
+            *c = alphabet[(value % radix) as usize];
+            value /= radix;
+        }
+
+        written -= count;
+    }
+
+    for c in out[..zeros].iter_mut() {
+        *c = alphabet[0];
+    }
+
+    Ok(len)
+}
+
+/// The bytes of `256^len`.
+fn one_followed_by(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0; len + 1];
+    bytes[0] = 1;
+    bytes
+}
+
+/// Convert `data` to limbs, splitting it until the pieces are short enough
+/// to convert directly. The low half is always `LEAF_LEN << level` bytes,
+/// so its weight is one of the precomputed `powers`.
+fn convert(data: &[u8], powers: &[Limbs], scale: u64) -> Limbs {
+    if data.len() <= LEAF_LEN {
+        return to_limbs(data, scale);
+    }
+
+    let mut level = 0;
+
+    while LEAF_LEN << (level + 1) < data.len() {
+        level += 1;
+    }
+
+    let (high, low) = data.split_at(data.len() - (LEAF_LEN << level));
+    let (high, low) = rayon::join(|| convert(high, powers, scale), || convert(low, powers, scale));
+
+    let mut limbs = multiply(&high, &powers[level], scale);
+    add(&mut limbs, &low, scale);
+    trim(&mut limbs);
+
+    limbs
+}
+
+/// Convert `data` to limbs a digit block at a time.
+fn to_limbs(data: &[u8], scale: u64) -> Limbs {
+    let mut limbs = Vec::with_capacity(data.len() / 3 + 1);
+
+    for chunk in data.chunks(4) {
+        let shift = 8 * chunk.len() as u32;
+        let mut carry = chunk.iter().fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+
+        for limb in limbs.iter_mut() {
+            carry += *limb << shift;
+            *limb = carry % scale;
+            carry /= scale;
+        }
+
+        while carry > 0 {
+            limbs.push(carry % scale);
+            carry /= scale;
+        }
+    }
+
+    limbs
+}
+
+/// Multiply two numbers, computing ranges of the output columns in
+/// parallel and propagating the carries afterwards.
+fn multiply(a: &[u64], b: &[u64], scale: u64) -> Limbs {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut columns = vec![0u128; a.len() + b.len() - 1];
+
+    columns.par_chunks_mut(COLUMNS_PER_TASK)
+        .enumerate()
+        .for_each(|(task, columns)| {
+            let first = task * COLUMNS_PER_TASK;
+
+            for (k, column) in (first..).zip(columns.iter_mut()) {
+                // Limbs are below 2^32, so every product fits a u64 and
+                // the sum of a column fits a u128.
+                let start = k.saturating_sub(b.len() - 1);
+                let end = (k + 1).min(a.len());
+
+                *column = (start..end).map(|i| u128::from(a[i] * b[k - i])).sum();
+            }
+        });
+
+    let mut limbs = Vec::with_capacity(columns.len() + 1);
+    let mut carry = 0u128;
+
+    for column in columns {
+        carry += column;
+        limbs.push((carry % u128::from(scale)) as u64);
+        carry /= u128::from(scale);
+    }
+
+    while carry > 0 {
+        limbs.push((carry % u128::from(scale)) as u64);
+        carry /= u128::from(scale);
+    }
+
+    trim(&mut limbs);
+    limbs
+}
+
+/// Add `b` to `a`.
+fn add(a: &mut Limbs, b: &[u64], scale: u64) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+
+    let mut carry = 0;
+
+    for (i, limb) in a.iter_mut().enumerate() {
+        if i >= b.len() && carry == 0 {
+            break;
+        }
+
+        let sum = *limb + b.get(i).cloned().unwrap_or(0) + carry;
+        *limb = sum % scale;
+        carry = sum / scale;
+    }
+
+    if carry > 0 {
+        a.push(carry);
+    }
+}
+
+/// Drop the zero limbs at the top.
+fn trim(limbs: &mut Limbs) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
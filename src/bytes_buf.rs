@@ -0,0 +1,66 @@
+//! Integration with the [bytes](https://docs.rs/bytes) crate.
+//!
+//! `Bytes` and `BytesMut` already implement `AsRef<[u8]>`, so they are
+//! `Encodable` as they are; this adds the decoding side.
+
+use bytes::{Bytes, BytesMut};
+use decodable::split;
+use {Base, Decodable, Result};
+
+/// Decode the string into `Bytes`. The decoded buffer is handed over
+/// without copying.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_bytes};
+///
+/// let (base, data) = decode_bytes("zCn8eVZg").unwrap();
+///
+/// assert_eq!(base, Base::Base58btc);
+/// assert_eq!(data, "hello");
+/// ```
+pub fn decode_bytes<T: Decodable>(data: T) -> Result<(Base, Bytes)> {
+    let decoded = data.decode()?;
+
+    Ok((decoded.base, Bytes::from(decoded.data)))
+}
+
+/// Decode the string, appending the raw binary data to a `BytesMut`.
+/// Returns the base and the number of bytes written; on error `out` is
+/// left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_into_bytes_mut};
+/// # extern crate bytes;
+/// # extern crate multibase;
+/// use bytes::BytesMut;
+///
+/// # fn main() {
+/// let mut buffer = BytesMut::from("id: ");
+///
+/// assert_eq!(decode_into_bytes_mut("zCn8eVZg", &mut buffer).unwrap(),
+///            (Base::Base58btc, 5));
+/// assert_eq!(buffer, "id: hello");
+/// # }
+/// ```
+pub fn decode_into_bytes_mut<T: AsRef<str>>(data: T, out: &mut BytesMut) -> Result<(Base, usize)> {
+    let data = data.as_ref();
+    let (base, _) = split(data)?;
+    let start = out.len();
+
+    out.resize(start + base.max_decoded_len(data.len()), 0);
+
+    match data.decode_slice(&mut out[start..]) {
+        Ok((base, written)) => {
+            out.truncate(start + written);
+            Ok((base, written))
+        }
+        Err(err) => {
+            out.truncate(start);
+            Err(err)
+        }
+    }
+}
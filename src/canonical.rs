@@ -0,0 +1,121 @@
+use std::fmt;
+
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+use alloc::string::String;
+
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+use {Base, Decodable, Decoded, Error, Result};
+
+/// Why a multibase string is not in its canonical form.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum NonCanonical {
+    /// Uses the uppercase variant of a base that ignores case.
+    Case,
+    /// Sets bits past the end of the data in its last character.
+    TrailingBits,
+    /// Carries missing or excess padding.
+    Padding,
+    /// Carries leading zero digits that don't stand for zero bytes.
+    LeadingZeros,
+}
+
+impl fmt::Display for NonCanonical {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::NonCanonical::*;
+
+        f.write_str(match *self {
+            Case => "uppercase variant of a case-insensitive base",
+            TrailingBits => "nonzero trailing bits",
+            Padding => "wrong padding",
+            LeadingZeros => "superfluous leading zero digits",
+        })
+    }
+}
+
+/// The base strings in `base` are canonically written in.
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+fn canonical_base(base: Base) -> Base {
+    base.name()
+        .strip_suffix("upper")
+        .and_then(|name| name.parse().ok())
+        .unwrap_or(base)
+}
+
+/// Rewrite the string in its canonical form, so that strings carrying the
+/// same data in the same base compare equal, for example as map keys.
+///
+/// Bases that differ only in letter case are folded into their lowercase
+/// variant, and the data is re-encoded, which clears any nonzero trailing
+/// bits and fixes the padding.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::normalize;
+///
+/// assert_eq!(normalize("F68656C6C6F").unwrap(), "f68656c6c6f");
+/// assert_eq!(normalize("cnbur====").unwrap(), "cnbuq====");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+pub fn normalize<T: AsRef<str>>(data: T) -> Result<String> {
+    let decoded = data.as_ref().decode()?;
+
+    Ok(decoded.encode(canonical_base(decoded.base)))
+}
+
+/// Check that the string is valid and already in the form `normalize`
+/// produces, failing with `Error::NonCanonical` and the reason if not.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{check_canonical, Error, NonCanonical};
+///
+/// assert_eq!(check_canonical("f68656c6c6f"), Ok(()));
+/// assert_eq!(check_canonical("F68656C6C6F"), Err(Error::NonCanonical(NonCanonical::Case)));
+/// assert_eq!(check_canonical("cnbur===="), Err(Error::NonCanonical(NonCanonical::TrailingBits)));
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+pub fn check_canonical<T: AsRef<str>>(data: T) -> Result<()> {
+    let data = data.as_ref();
+
+    check_decoded(data, &data.decode()?)
+}
+
+/// Check that `data`, which decoded to `decoded`, is in canonical form.
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+pub(crate) fn check_decoded(data: &str, decoded: &Decoded) -> Result<()> {
+    if canonical_base(decoded.base) != decoded.base {
+        return Err(Error::NonCanonical(NonCanonical::Case));
+    }
+
+    let canonical = decoded.encode(decoded.base);
+
+    if canonical == data {
+        Ok(())
+    } else if canonical.eq_ignore_ascii_case(data) {
+        Err(Error::NonCanonical(NonCanonical::Case))
+    } else if canonical.len() != data.len() && decoded.base.is_bitwise() {
+        Err(Error::NonCanonical(NonCanonical::Padding))
+    } else if canonical.len() != data.len() {
+        Err(Error::NonCanonical(NonCanonical::LeadingZeros))
+    } else {
+        Err(Error::NonCanonical(NonCanonical::TrailingBits))
+    }
+}
+
+/// Whether the string is valid and in canonical form.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::is_canonical;
+///
+/// assert!(is_canonical("MZg=="));
+/// assert!(!is_canonical("MZh=="));
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+#[inline]
+pub fn is_canonical<T: AsRef<str>>(data: T) -> bool {
+    check_canonical(data).is_ok()
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "encode")]
+use alloc::string::String;
+#[cfg(feature = "encode")]
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+#[cfg(feature = "encode")]
+use {Base, Encodable};
+#[cfg(feature = "decode")]
+use {Decodable, Decoded, Error, Result};
+
+fn checksum(data: &[u8]) -> [u8; 4] {
+    let hash = Sha256::digest(Sha256::digest(data));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Encode with the given base, appending the 4-byte double-SHA256 checksum
+/// used by Base58Check. With `Base58btc` this yields a Bitcoin-style
+/// address behind the multibase prefix.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_check};
+///
+/// assert_eq!(encode_check(Base::Base58btc, b"hello"),
+///            "z2L5B5yqsVG8Vt");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_check<T: AsRef<[u8]>>(base: Base, data: T) -> String {
+    let data = data.as_ref();
+    let mut payload = Vec::with_capacity(data.len() + 4);
+
+    payload.extend_from_slice(data);
+    payload.extend_from_slice(&checksum(data));
+    payload.encode(base)
+}
+
+/// Decode the string and verify its trailing double-SHA256 checksum,
+/// returning the data with the checksum stripped.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_check};
+///
+/// assert_eq!(decode_check("z2L5B5yqsVG8Vt").unwrap(),
+///            (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_check<T: Decodable>(data: T) -> Result<Decoded> {
+    let Decoded { base, data: mut decoded } = data.decode()?;
+
+    if decoded.len() < 4 {
+        return Err(Error::InvalidChecksum);
+    }
+
+    let split = decoded.len() - 4;
+
+    if checksum(&decoded[..split]) != decoded[split..] {
+        return Err(Error::InvalidChecksum);
+    }
+
+    decoded.truncate(split);
+    Ok(Decoded { base, data: decoded })
+}
@@ -0,0 +1,192 @@
+//! Zero-sized types for every base, so the base can be chosen at compile
+//! time through the `BaseCodec` trait, and the object-safe `Codec` trait
+//! with a `Registry` for choosing encodings at runtime.
+//!
+//! # Examples
+//!
+//! ```
+//! use multibase::codec::{BaseCodec, Base58btc};
+//!
+//! fn address<C: BaseCodec>(key: &[u8]) -> String {
+//!     C::encode(key)
+//! }
+//!
+//! assert_eq!(address::<Base58btc>(b"hello"), "zCn8eVZg");
+//! ```
+
+use alloc::boxed::Box;
+#[cfg(feature = "encode")]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use {Base, Result};
+#[cfg(feature = "decode")]
+use Error;
+
+/// A base fixed at compile time.
+pub trait BaseCodec {
+    /// The base this codec implements.
+    const BASE: Base;
+
+    /// Encode with the base, prefix included.
+    #[cfg(feature = "encode")]
+    #[inline]
+    fn encode<T: AsRef<[u8]>>(data: T) -> String {
+        Self::BASE.encode(data)
+    }
+
+    /// Encode with the base, leaving out the prefix.
+    #[cfg(feature = "encode")]
+    #[inline]
+    fn encode_raw<T: AsRef<[u8]>>(data: T) -> String {
+        Self::BASE.encode_raw(data)
+    }
+
+    /// Decode a string encoded with the base that has no prefix.
+    #[cfg(feature = "decode")]
+    #[inline]
+    fn decode_raw<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+        Self::BASE.decode_raw(data)
+    }
+}
+
+/// An encoding that can be chosen at runtime, such as from a config file.
+///
+/// Implemented by `Base`, the zero-sized codecs in this module, and the
+/// extension encodings that have no multibase code. Applications can
+/// implement it to plug in their own.
+pub trait Codec {
+    /// Name of the encoding, such as `base58btc`.
+    fn name(&self) -> &str;
+
+    /// Multibase prefix, or `None` for encodings that have no code.
+    fn code(&self) -> Option<char>;
+
+    /// Encode, leaving out the prefix.
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String>;
+
+    /// Decode a string that has no prefix.
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>>;
+}
+
+impl Codec for Base {
+    fn name(&self) -> &str {
+        Base::name(self)
+    }
+
+    fn code(&self) -> Option<char> {
+        Some(Base::code(self))
+    }
+
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String> {
+        Ok(Base::encode_raw(self, data))
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+        Base::decode_raw(self, data)
+    }
+}
+
+impl<C: BaseCodec> Codec for C {
+    fn name(&self) -> &str {
+        C::BASE.name()
+    }
+
+    fn code(&self) -> Option<char> {
+        Some(C::BASE.code())
+    }
+
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String> {
+        Ok(C::BASE.encode_raw(data))
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+        C::BASE.decode_raw(data)
+    }
+}
+
+/// A set of codecs looked up by name or prefix at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{z85, Base};
+/// use multibase::codec::Registry;
+///
+/// let mut registry = Registry::new();
+/// registry.register(z85::Z85);
+///
+/// let codec = registry.get("z85").unwrap();
+/// assert_eq!(codec.encode_raw(b"\x86\x4f\xd2\x6f").unwrap(), "Hello");
+///
+/// let (codec, data) = registry.decode("zCn8eVZg").unwrap();
+/// assert_eq!((codec.name(), data), ("base58btc", b"hello".to_vec()));
+/// ```
+pub struct Registry {
+    codecs: Vec<Box<dyn Codec>>,
+}
+
+impl Registry {
+    /// Create a registry holding every base.
+    pub fn new() -> Registry {
+        Registry {
+            codecs: Base::ALL.iter().map(|&base| Box::new(base) as Box<dyn Codec>).collect(),
+        }
+    }
+
+    /// Add a codec. It shadows any codec registered earlier under the same
+    /// name or prefix.
+    pub fn register<C: Codec + 'static>(&mut self, codec: C) {
+        self.codecs.push(Box::new(codec));
+    }
+
+    /// Look up a codec by name.
+    pub fn get(&self, name: &str) -> Option<&dyn Codec> {
+        self.codecs.iter().rev().find(|codec| codec.name() == name).map(|codec| &**codec)
+    }
+
+    /// Look up a codec by multibase prefix.
+    pub fn from_code(&self, code: char) -> Option<&dyn Codec> {
+        self.codecs.iter().rev().find(|codec| codec.code() == Some(code)).map(|codec| &**codec)
+    }
+
+    /// Decode a prefixed string with whichever registered codec owns the
+    /// prefix.
+    #[cfg(feature = "decode")]
+    pub fn decode(&self, data: &str) -> Result<(&dyn Codec, Vec<u8>)> {
+        let code = data.chars().next().ok_or(Error::EmptyInput)?;
+        let codec = self.from_code(code).ok_or(Error::UnkownBase)?;
+        let decoded = codec.decode_raw(&data[code.len_utf8()..])
+            .map_err(|err| err.offset(code.len_utf8()))?;
+
+        Ok((codec, decoded))
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Registry {
+        Registry::new()
+    }
+}
+
+macro_rules! build_codecs {
+    {$( $(#[$attr:meta])* $val:literal => $var:ident, $module:ident: $name:literal, $alph:literal ($status:ident, $padded:literal), )*} => {
+        $(
+            $(#[$attr])*
+            #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+            pub struct $var;
+
+            impl BaseCodec for $var {
+                const BASE: Base = Base::$var;
+            }
+        )*
+    }
+}
+
+for_each_base!(build_codecs);
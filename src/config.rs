@@ -0,0 +1,224 @@
+use std::iter;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "encode")]
+use canonical;
+use decodable::split;
+use rfc4648;
+use {Base, Decoded, Error, Result, Status};
+
+/// Options for `decode_with`, bundling the knobs that tolerate or reject
+/// input beyond what plain `decode` accepts.
+///
+/// The default configuration behaves exactly like `decode`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, DecodeConfig, Error, decode_with};
+///
+/// let config = DecodeConfig::new()
+///     .case_insensitive(true)
+///     .skip_whitespace(true)
+///     .allowed_bases(&[Base::Base32, Base::Base32Upper]);
+///
+/// assert_eq!(decode_with("bNBSW Y3DP", &config).unwrap(),
+///            (Base::Base32, b"hello".to_vec()));
+/// assert_eq!(decode_with("zCn8eVZg", &config), Err(Error::BaseNotAllowed(Base::Base58btc)));
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct DecodeConfig {
+    case_insensitive: bool,
+    reject_mixed_case: bool,
+    skip_whitespace: bool,
+    allow_missing_padding: bool,
+    strip_padding: bool,
+    stable_only: bool,
+    #[cfg(feature = "encode")]
+    canonical_only: bool,
+    allowed_bases: Option<Vec<Base>>,
+    max_len: Option<usize>,
+}
+
+impl DecodeConfig {
+    /// The default configuration, as strict as `decode`.
+    #[inline]
+    pub fn new() -> DecodeConfig {
+        DecodeConfig::default()
+    }
+
+    /// Accept either letter case in the payload of bases whose alphabet
+    /// doesn't rely on case.
+    pub fn case_insensitive(mut self, enabled: bool) -> DecodeConfig {
+        self.case_insensitive = enabled;
+        self
+    }
+
+    /// Reject payloads that mix upper and lower case in bases whose
+    /// alphabet doesn't rely on case, a common sign of corruption or
+    /// tampering, with `Error::MixedCase`.
+    pub fn reject_mixed_case(mut self, enabled: bool) -> DecodeConfig {
+        self.reject_mixed_case = enabled;
+        self
+    }
+
+    /// Skip ASCII whitespace anywhere in the input.
+    pub fn skip_whitespace(mut self, enabled: bool) -> DecodeConfig {
+        self.skip_whitespace = enabled;
+        self
+    }
+
+    /// Accept input in padded bases that leaves out its trailing padding.
+    pub fn allow_missing_padding(mut self, enabled: bool) -> DecodeConfig {
+        self.allow_missing_padding = enabled;
+        self
+    }
+
+    /// Ignore any trailing padding, whether the base is padded or not.
+    /// Padded bases are re-padded as needed, so this implies
+    /// `allow_missing_padding`.
+    pub fn strip_padding(mut self, enabled: bool) -> DecodeConfig {
+        self.strip_padding = enabled;
+        self
+    }
+
+    /// Reject bases that are still draft or experimental in the spec, with
+    /// `Error::UnstableBase`.
+    pub fn stable_only(mut self, enabled: bool) -> DecodeConfig {
+        self.stable_only = enabled;
+        self
+    }
+
+    /// Reject input that is not in the canonical form `normalize`
+    /// produces, with `Error::NonCanonical`, so that every piece of data
+    /// has exactly one accepted encoding in each base. Checked after any
+    /// whitespace has been skipped.
+    #[cfg(feature = "encode")]
+    pub fn canonical_only(mut self, enabled: bool) -> DecodeConfig {
+        self.canonical_only = enabled;
+        self
+    }
+
+    /// Only accept the given bases, rejecting others with
+    /// `Error::BaseNotAllowed`.
+    pub fn allowed_bases(mut self, bases: &[Base]) -> DecodeConfig {
+        self.allowed_bases = Some(bases.to_vec());
+        self
+    }
+
+    /// Reject input longer than `len` bytes with `Error::InvalidLength`,
+    /// before doing any work on it.
+    pub fn max_len(mut self, len: usize) -> DecodeConfig {
+        self.max_len = Some(len);
+        self
+    }
+}
+
+/// Decode the string according to `config`.
+///
+/// Error positions refer to the original input.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, DecodeConfig, decode_with};
+///
+/// let config = DecodeConfig::new().allow_missing_padding(true);
+///
+/// assert_eq!(decode_with("MaGVsbG8", &config).unwrap(),
+///            (Base::Base64pad, b"hello".to_vec()));
+/// ```
+pub fn decode_with<T: AsRef<str>>(data: T, config: &DecodeConfig) -> Result<Decoded> {
+    let data = data.as_ref();
+
+    if config.max_len.is_some_and(|max| data.len() > max) {
+        return Err(Error::InvalidLength);
+    }
+
+    if !config.skip_whitespace {
+        return decode_configured(data, config);
+    }
+
+    let mut stripped = String::with_capacity(data.len());
+    stripped.extend(data.chars().filter(|c| !c.is_ascii_whitespace()));
+
+    decode_configured(&stripped, config).map_err(|err| {
+        err.map_position(|position| {
+            data.bytes()
+                .enumerate()
+                .filter(|&(_, c)| !c.is_ascii_whitespace())
+                .nth(position)
+                .map_or(data.len(), |(i, _)| i)
+        })
+    })
+}
+
+fn decode_configured(data: &str, config: &DecodeConfig) -> Result<Decoded> {
+    let (base, payload) = split(data)?;
+
+    if let Some(ref allowed) = config.allowed_bases {
+        if !allowed.contains(&base) {
+            return Err(Error::BaseNotAllowed(base));
+        }
+    }
+
+    if config.stable_only {
+        match base.status() {
+            Status::Default | Status::Candidate => {}
+            Status::Draft | Status::Experimental => return Err(Error::UnstableBase),
+        }
+    }
+
+    let prefix = data.len() - payload.len();
+    let mut payload = Cow::Borrowed(payload);
+
+    if config.reject_mixed_case && !base.is_case_sensitive()
+        && payload.bytes().any(|c| c.is_ascii_lowercase())
+        && payload.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return Err(Error::MixedCase);
+    }
+
+    if config.case_insensitive && !base.is_case_sensitive() {
+        if base.alphabet().iter().any(u8::is_ascii_lowercase) {
+            payload = Cow::Owned(payload.to_ascii_lowercase());
+        } else {
+            payload = Cow::Owned(payload.to_ascii_uppercase());
+        }
+    }
+
+    if config.strip_padding && payload.ends_with('=') {
+        payload = match payload {
+            Cow::Borrowed(payload) => Cow::Borrowed(payload.trim_end_matches('=')),
+            Cow::Owned(mut payload) => {
+                let len = payload.trim_end_matches('=').len();
+                payload.truncate(len);
+                Cow::Owned(payload)
+            }
+        };
+    }
+
+    if (config.allow_missing_padding || config.strip_padding) && base.is_padded() && !payload.ends_with('=') {
+        let missing = rfc4648::missing_padding(base.alphabet(), payload.len());
+        let mut padded = String::with_capacity(payload.len() + missing);
+
+        padded.push_str(&payload);
+        padded.extend(iter::repeat_n('=', missing));
+        payload = Cow::Owned(padded);
+    }
+
+    let decoded = Decoded {
+        base,
+        data: base.decode_raw(&*payload).map_err(|err| err.offset(prefix))?,
+    };
+
+    #[cfg(feature = "encode")]
+    if config.canonical_only {
+        canonical::check_decoded(data, &decoded)?;
+    }
+
+    Ok(decoded)
+}
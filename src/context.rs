@@ -0,0 +1,77 @@
+use std::str;
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "decode")]
+use decodable::split;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(feature = "decode")]
+use Result;
+use Base;
+
+/// Encode and decode through buffers owned by the context, so that repeated
+/// calls in a loop don't allocate once the buffers have grown to fit.
+///
+/// The results borrow the context and are valid until the next call. The
+/// decode tables are built at compile time and shared by every context, so
+/// keeping one around only saves the buffers.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Context};
+///
+/// let mut context = Context::new();
+///
+/// assert_eq!(context.encode(Base::Base58btc, b"hello"), "zCn8eVZg");
+/// assert_eq!(context.decode("f776f726c64").unwrap(), (Base::Base16, &b"world"[..]));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Context {
+    #[cfg(feature = "encode")]
+    encoded: Vec<u8>,
+    #[cfg(feature = "decode")]
+    decoded: Vec<u8>,
+}
+
+impl Context {
+    /// Create a context with empty buffers.
+    pub fn new() -> Context {
+        Context::default()
+    }
+
+    /// Create a context with buffers that fit `len` bytes of either output
+    /// before growing.
+    pub fn with_capacity(len: usize) -> Context {
+        Context {
+            #[cfg(feature = "encode")]
+            encoded: Vec::with_capacity(len),
+            #[cfg(feature = "decode")]
+            decoded: Vec::with_capacity(len),
+        }
+    }
+
+    /// Encode with the given base, prefix included.
+    #[cfg(feature = "encode")]
+    pub fn encode<T: Encodable>(&mut self, base: Base, data: T) -> &str {
+        self.encoded.clear();
+        data.encode_into(base, &mut self.encoded);
+
+        // Prefixes are chars and alphabets are ASCII.
+        unsafe { str::from_utf8_unchecked(&self.encoded) }
+    }
+
+    /// Decode a multibase string, returning its base and data.
+    #[cfg(feature = "decode")]
+    pub fn decode<T: AsRef<str>>(&mut self, data: T) -> Result<(Base, &[u8])> {
+        let data = data.as_ref();
+        let (base, payload) = split(data)?;
+        self.decoded.resize(base.max_raw_decoded_len(payload.len()), 0);
+
+        let written = base.decode_raw_slice(payload, &mut self.decoded)
+            .map_err(|err| err.offset(data.len() - payload.len()))?;
+
+        Ok((base, &self.decoded[..written]))
+    }
+}
@@ -1,25 +1,260 @@
-use {base_x, Base, Error, Result};
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use {decode_with, DecodeConfig};
+use {Base, Error, Result};
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use Encodable;
+
+/// Split the input into its base and payload.
+pub(crate) fn split(data: &str) -> Result<(Base, &str)> {
+    let code = data.chars().next().ok_or(Error::EmptyInput)?;
+    let base = Base::from_code(code)?;
+
+    Ok((base, &data[code.len_utf8()..]))
+}
+
+/// Data decoded from a multibase string, along with its base.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{decode, Base};
+///
+/// let decoded = decode("zCn8eVZg").unwrap();
+///
+/// assert_eq!(decoded.base, Base::Base58btc);
+/// assert_eq!(decoded.as_slice(), b"hello");
+/// assert_eq!(decoded.encode(Base::Base16), "f68656c6c6f");
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Decoded {
+    /// The base the data was encoded with.
+    pub base: Base,
+    /// The decoded data.
+    pub data: Vec<u8>,
+}
+
+#[cfg(feature = "alloc")]
+impl Decoded {
+    /// Get the decoded data.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Unwrap the decoded data.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Re-encode the data, possibly in another base.
+    #[cfg(all(feature = "encode", feature = "decode"))]
+    #[inline]
+    pub fn encode(&self, base: Base) -> String {
+        self.data.encode(base)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsRef<[u8]> for Decoded {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Decoded> for (Base, Vec<u8>) {
+    #[inline]
+    fn from(decoded: Decoded) -> (Base, Vec<u8>) {
+        (decoded.base, decoded.data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<(Base, Vec<u8>)> for Decoded {
+    fn eq(&self, other: &(Base, Vec<u8>)) -> bool {
+        self.base == other.0 && self.data == other.1
+    }
+}
+
+/// Result of a lenient decode: either decoded data, or the prefix and
+/// untouched payload of a base this version doesn't know.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{decode_lenient, Lenient};
+///
+/// match decode_lenient("Xllll").unwrap() {
+///     Lenient::Known(decoded) => println!("{:?}", decoded),
+///     Lenient::Unknown(code, payload) => assert_eq!((code, payload), ('X', "llll")),
+/// }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Lenient<'a> {
+    /// A known base, decoded.
+    Known(Decoded),
+    /// An unrecognized prefix, and the payload after it.
+    Unknown(char, &'a str),
+}
+
+/// Decode the string, passing strings with an unrecognized prefix through
+/// instead of failing, so that bases added to the spec later survive a
+/// round trip. Invalid payloads in known bases are still errors.
+#[cfg(feature = "alloc")]
+pub fn decode_lenient(data: &str) -> Result<Lenient<'_>> {
+    match data.decode() {
+        Ok(decoded) => Ok(Lenient::Known(decoded)),
+        Err(Error::UnkownBase) => {
+            let code = data.chars().next().expect("an empty input is not an unknown base");
+            Ok(Lenient::Unknown(code, &data[code.len_utf8()..]))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Decode the string, also accepting common conventions that are not
+/// multibase, for tools that ingest hand-written input:
+///
+/// - `0x`-prefixed hex, in either case, is read as `Base16`;
+/// - bare Base64 ending in `=` padding is read as `Base64pad`, or
+///   `Base64urlpad` if it uses the URL-safe alphabet.
+///
+/// Each convention is only recognized with the feature of its base.
+///
+/// Anything else is decoded as multibase, and fails the same way.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_permissive};
+///
+/// assert_eq!(decode_permissive("0x68656C6C6F").unwrap(), (Base::Base16, b"hello".to_vec()));
+/// assert_eq!(decode_permissive("aGVsbG8=").unwrap(), (Base::Base64pad, b"hello".to_vec()));
+/// assert_eq!(decode_permissive("zCn8eVZg").unwrap(), (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_permissive(data: &str) -> Result<Decoded> {
+    #[cfg(feature = "base16")]
+    if let Some(hex) = data.strip_prefix("0x").or_else(|| data.strip_prefix("0X")) {
+        let base = Base::Base16;
+        let data = base.decode_raw(hex.to_ascii_lowercase()).map_err(|err| err.offset(2))?;
+
+        return Ok(Decoded { base, data });
+    }
+
+    let err = match data.decode() {
+        Ok(decoded) => return Ok(decoded),
+        Err(err) => err,
+    };
+
+    #[cfg(feature = "base64")]
+    if data.ends_with('=') {
+        for &base in &[Base::Base64pad, Base::Base64urlpad] {
+            if let Ok(decoded) = base.decode_raw(data) {
+                return Ok(Decoded { base, data: decoded });
+            }
+        }
+    }
+
+    Err(err)
+}
+
+/// Decode the string, skipping ASCII whitespace anywhere in it, so that
+/// content wrapped in emails or YAML block scalars decodes as is. Error
+/// positions refer to the original input.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_skip_whitespace};
+///
+/// assert_eq!(decode_skip_whitespace("  MaGVs\n\tbG8=\n").unwrap(),
+///            (Base::Base64pad, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_skip_whitespace<T: AsRef<str>>(data: T) -> Result<Decoded> {
+    decode_with(data, &DecodeConfig::new().skip_whitespace(true))
+}
 
 /// Trait implemented for string-like types.
 pub trait Decodable {
     /// Decode to base and raw binary data
-    fn decode(&self) -> Result<(Base, Vec<u8>)>;
+    #[cfg(feature = "alloc")]
+    fn decode(&self) -> Result<Decoded>;
+
+    /// Decode, appending the raw binary data to `out`. Returns the base and
+    /// the number of bytes written; on error `out` is left unchanged.
+    #[cfg(feature = "alloc")]
+    fn decode_into(&self, out: &mut Vec<u8>) -> Result<(Base, usize)>;
+
+    /// Decode into `out` without allocating. Returns the base and the number
+    /// of bytes written; the contents of `out` are unspecified on error.
+    fn decode_slice(&self, out: &mut [u8]) -> Result<(Base, usize)>;
 }
 
 impl Decodable for str {
-    fn decode(&self) -> Result<(Base, Vec<u8>)> {
-        let code = self.chars().next().ok_or(Error::InvalidBaseString)?;
-        let base = Base::from_code(code)?;
-        let content = &self[code.len_utf8()..];
-        let alphabet = base.alphabet();
-        let decoded = base_x::decode(alphabet, content)?;
-        Ok((base, decoded))
-     }
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode(&self) -> Result<Decoded> {
+        let mut data = Vec::new();
+        let (base, _) = self.decode_into(&mut data)?;
+        Ok(Decoded { base, data })
+    }
+
+    #[cfg(feature = "alloc")]
+    fn decode_into(&self, out: &mut Vec<u8>) -> Result<(Base, usize)> {
+        let (base, _) = split(self)?;
+        let start = out.len();
+
+        out.resize(start + base.max_decoded_len(self.len()), 0);
+
+        match self.decode_slice(&mut out[start..]) {
+            Ok((base, written)) => {
+                out.truncate(start + written);
+                Ok((base, written))
+            }
+            Err(err) => {
+                out.truncate(start);
+                Err(err)
+            }
+        }
+    }
+
+    fn decode_slice(&self, out: &mut [u8]) -> Result<(Base, usize)> {
+        let (base, content) = split(self)?;
+
+        let written = base.decode_raw_slice(content, out)
+            .map_err(|err| err.offset(self.len() - content.len()))?;
+
+        Ok((base, written))
+    }
 }
 
-impl<'a, D: AsRef<str>> Decodable for D {
+impl<D: AsRef<str>> Decodable for D {
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn decode(&self) -> Result<(Base, Vec<u8>)> {
+    fn decode(&self) -> Result<Decoded> {
         self.as_ref().decode()
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn decode_into(&self, out: &mut Vec<u8>) -> Result<(Base, usize)> {
+        self.as_ref().decode_into(out)
+    }
+
+    #[inline]
+    fn decode_slice(&self, out: &mut [u8]) -> Result<(Base, usize)> {
+        self.as_ref().decode_slice(out)
+    }
 }
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::str;
+
+use rfc4648;
+use Base;
+
+/// Number of characters encoded on the stack at a time.
+const CHUNK: usize = 256;
+
+/// Lazily encoded data, written straight into the formatter when
+/// displayed. Returned by [`display`](fn.display.html).
+#[derive(Clone, Copy, Debug)]
+pub struct Encoded<'a> {
+    base: Base,
+    data: &'a [u8],
+}
+
+/// Encode with the given base when formatted, without building a `String`
+/// first. Handy in `format!`, logging fields and error messages.
+///
+/// Bitwise bases are encoded through a small stack buffer. The others
+/// treat the data as one big number, so larger inputs still need a
+/// temporary buffer, and fail to format without the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, display};
+///
+/// assert_eq!(format!("id: {}", display(Base::Base58btc, b"hello")),
+///            "id: zCn8eVZg");
+/// ```
+#[inline]
+pub fn display(base: Base, data: &[u8]) -> Encoded<'_> {
+    Encoded { base, data }
+}
+
+impl<'a> fmt::Display for Encoded<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let base = self.base;
+        let mut buffer = [0; CHUNK];
+
+        write!(f, "{}", base.code())?;
+
+        if base.is_bitwise() {
+            let block = rfc4648::block_bytes(base.alphabet());
+            let chunk = block * (CHUNK / base.max_raw_encoded_len(block));
+
+            for data in self.data.chunks(chunk) {
+                write_raw(f, base, data, &mut buffer)?;
+            }
+
+            Ok(())
+        } else if base.max_raw_encoded_len(self.data.len()) <= CHUNK {
+            write_raw(f, base, self.data, &mut buffer)
+        } else {
+            write_large(f, base, self.data)
+        }
+    }
+}
+
+fn write_raw(f: &mut fmt::Formatter, base: Base, data: &[u8], buffer: &mut [u8]) -> fmt::Result {
+    let written = base.encode_raw_slice(data, buffer)
+        .expect("buffer is sized to the maximum encoded length");
+
+    // Alphabets are ASCII.
+    f.write_str(unsafe { str::from_utf8_unchecked(&buffer[..written]) })
+}
+
+/// Encode data too large for the stack buffer in a non-bitwise base.
+#[cfg(feature = "alloc")]
+fn write_large(f: &mut fmt::Formatter, base: Base, data: &[u8]) -> fmt::Result {
+    f.write_str(&base.encode_raw(data))
+}
+
+#[cfg(not(feature = "alloc"))]
+fn write_large(_: &mut fmt::Formatter, _: Base, _: &[u8]) -> fmt::Result {
+    Err(fmt::Error)
+}
@@ -1,25 +1,80 @@
-use {base_x, Base};
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use {Base, Error, Result};
 
 /// Trait implemented for byte-array-like types.
 pub trait Encodable {
-    /// Encode with the given base
+    /// Encode with the given base, prefix included
+    #[cfg(feature = "alloc")]
     fn encode(&self, base: Base) -> String;
+
+    /// Encode with the given base, appending to `out`. Returns the number
+    /// of bytes written.
+    #[cfg(feature = "alloc")]
+    fn encode_into(&self, base: Base, out: &mut Vec<u8>) -> usize;
+
+    /// Encode with the given base into `out` without allocating. Returns the
+    /// number of bytes written; the contents of `out` are unspecified on
+    /// error, and past the encoding up to `Base::max_encoded_len` on
+    /// success.
+    fn encode_slice(&self, base: Base, out: &mut [u8]) -> Result<usize>;
 }
 
 impl Encodable for [u8] {
+    #[cfg(feature = "alloc")]
     #[inline]
     fn encode(&self, base: Base) -> String {
-        let alphabet = base.alphabet();
+        let mut encoded = Vec::new();
+        self.encode_into(base, &mut encoded);
+
+        // Prefixes are chars and alphabets are ASCII.
+        unsafe { String::from_utf8_unchecked(encoded) }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn encode_into(&self, base: Base, out: &mut Vec<u8>) -> usize {
+        let start = out.len();
+        out.resize(start + base.max_encoded_len(self.len()), 0);
+
+        let written = self.encode_slice(base, &mut out[start..])
+            .expect("buffer is sized to the maximum encoded length");
+
+        out.truncate(start + written);
+        written
+    }
 
-        let mut encoded = base_x::encode(alphabet, self);
-        encoded.insert(0, base.code());
-        encoded
+    fn encode_slice(&self, base: Base, out: &mut [u8]) -> Result<usize> {
+        let code = base.code();
+
+        if out.len() < code.len_utf8() {
+            return Err(Error::BufferTooSmall);
+        }
+
+        let (prefix, payload) = out.split_at_mut(code.len_utf8());
+        code.encode_utf8(prefix);
+
+        Ok(prefix.len() + base.encode_raw_slice(self, payload)?)
     }
 }
 
-impl<'a, E: AsRef<[u8]>> Encodable for E {
+impl<E: AsRef<[u8]>> Encodable for E {
+    #[cfg(feature = "alloc")]
     #[inline]
     fn encode(&self, base: Base) -> String {
         self.as_ref().encode(base)
     }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn encode_into(&self, base: Base, out: &mut Vec<u8>) -> usize {
+        self.as_ref().encode_into(base, out)
+    }
+
+    #[inline]
+    fn encode_slice(&self, base: Base, out: &mut [u8]) -> Result<usize> {
+        self.as_ref().encode_slice(base, out)
+    }
 }
@@ -1,34 +1,145 @@
-use std::{fmt, error};
-use base_x;
+use std::{fmt, error, str};
+
+use {Base, NonCanonical};
 
 /// Error types
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum Error {
     UnkownBase,
-    InvalidBaseString,
+    InvalidBaseString(DecodeError),
+    EmptyInput,
+    InvalidLength,
+    InvalidChecksum,
+    UnstableBase,
+    BufferTooSmall,
+    WrongBase { expected: Base, found: Base },
+    BaseNotAllowed(Base),
+    MixedCase,
+    OutOfMemory,
+    NonCanonical(NonCanonical),
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
 
+/// Why a string failed to decode, the source of `Error::InvalidBaseString`.
+/// Positions are byte offsets into the input.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DecodeError {
+    /// A character that is not in the alphabet.
+    InvalidCharacter { character: char, position: usize },
+    /// Missing, excess or misplaced padding.
+    InvalidPadding { position: usize },
+    /// A block of characters whose value doesn't fit its bytes.
+    Overflow { position: usize },
+    /// Input bytes that are not valid UTF-8.
+    InvalidUtf8 { position: usize },
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(error::Error::description(self))
+        use Error::*;
+
+        match *self {
+            UnkownBase => f.write_str("Unkown base"),
+            InvalidBaseString(_) => f.write_str("Invalid base string"),
+            EmptyInput => f.write_str("Empty input"),
+            InvalidLength => f.write_str("Invalid input length"),
+            InvalidChecksum => f.write_str("Invalid checksum"),
+            UnstableBase => f.write_str("Base is not stable in the multibase spec"),
+            BufferTooSmall => f.write_str("Output buffer too small"),
+            WrongBase { expected, found } => write!(f, "Expected {}, found {}", expected, found),
+            BaseNotAllowed(base) => write!(f, "Base {} is not allowed", base),
+            MixedCase => f.write_str("Payload mixes upper and lower case"),
+            OutOfMemory => f.write_str("Out of memory"),
+            NonCanonical(reason) => write!(f, "Non-canonical encoding: {}", reason),
+        }
     }
 }
 
-impl error::Error for Error {
-    fn description(&self) -> &str {
-        use Error::*;
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::DecodeError::*;
 
         match *self {
-            UnkownBase => "Unkown base",
-            InvalidBaseString => "Invalid base string",
+            InvalidCharacter { character, position } => {
+                write!(f, "Invalid character {:?} at position {}", character, position)
+            }
+            InvalidPadding { position } => write!(f, "Invalid padding at position {}", position),
+            Overflow { position } => write!(f, "Block at position {} overflows", position),
+            InvalidUtf8 { position } => write!(f, "Invalid UTF-8 at position {}", position),
+        }
+    }
+}
+
+impl DecodeError {
+    /// Map the position through `f`.
+    #[cfg(feature = "decode")]
+    fn map_position<F: FnOnce(usize) -> usize>(self, f: F) -> DecodeError {
+        use self::DecodeError::*;
+
+        match self {
+            InvalidCharacter { character, position } => {
+                InvalidCharacter { character, position: f(position) }
+            }
+            InvalidPadding { position } => InvalidPadding { position: f(position) },
+            Overflow { position } => Overflow { position: f(position) },
+            InvalidUtf8 { position } => InvalidUtf8 { position: f(position) },
+        }
+    }
+}
+
+impl Error {
+    /// An `InvalidCharacter` error for the character starting at byte
+    /// `position` of `input`.
+    #[cfg(feature = "decode")]
+    pub(crate) fn invalid_character(input: &[u8], position: usize) -> Error {
+        let character = str::from_utf8(&input[position..]).ok()
+            .and_then(|rest| rest.chars().next())
+            .unwrap_or(char::from(input[position]));
+
+        DecodeError::InvalidCharacter { character, position }.into()
+    }
+
+    /// Shift the position of the error by `by` bytes, for errors raised on
+    /// a part of the input, such as the payload after the prefix.
+    #[cfg(feature = "decode")]
+    #[inline]
+    pub(crate) fn offset(self, by: usize) -> Error {
+        self.map_position(|position| position + by)
+    }
+
+    /// Map the position of the error through `f`, for errors raised on a
+    /// transformed copy of the input.
+    #[cfg(feature = "decode")]
+    pub(crate) fn map_position<F: FnOnce(usize) -> usize>(self, f: F) -> Error {
+        match self {
+            Error::InvalidBaseString(err) => Error::InvalidBaseString(err.map_position(f)),
+            err => err,
         }
     }
 }
 
-impl From<base_x::DecodeError> for Error {
-    fn from(_: base_x::DecodeError) -> Error {
-        Error::InvalidBaseString
+impl From<DecodeError> for Error {
+    #[inline]
+    fn from(err: DecodeError) -> Error {
+        Error::InvalidBaseString(err)
     }
 }
+
+impl From<str::Utf8Error> for Error {
+    #[inline]
+    fn from(err: str::Utf8Error) -> Error {
+        DecodeError::InvalidUtf8 { position: err.valid_up_to() }.into()
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::InvalidBaseString(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl error::Error for DecodeError {}
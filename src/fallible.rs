@@ -0,0 +1,65 @@
+#[cfg(feature = "encode")]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "decode")]
+use decodable::split;
+#[cfg(feature = "decode")]
+use {Decodable, Decoded};
+#[cfg(feature = "encode")]
+use {Base, Encodable};
+use {Error, Result};
+
+/// Allocate a zeroed buffer of `len` bytes, failing instead of aborting.
+fn try_buffer(len: usize) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    buffer.try_reserve_exact(len).map_err(|_| Error::OutOfMemory)?;
+    buffer.resize(len, 0);
+
+    Ok(buffer)
+}
+
+/// Encode with the given base, failing with `Error::OutOfMemory` instead of
+/// aborting if the output can't be allocated.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, try_encode};
+///
+/// assert_eq!(try_encode(Base::Base58btc, b"hello").unwrap(), "zCn8eVZg");
+/// ```
+#[cfg(feature = "encode")]
+pub fn try_encode<T: AsRef<[u8]>>(base: Base, data: T) -> Result<String> {
+    let data = data.as_ref();
+    let mut encoded = try_buffer(base.max_encoded_len(data.len()))?;
+
+    let written = data.encode_slice(base, &mut encoded)?;
+    encoded.truncate(written);
+
+    // Prefixes are chars and alphabets are ASCII.
+    Ok(unsafe { String::from_utf8_unchecked(encoded) })
+}
+
+/// Decode the string, failing with `Error::OutOfMemory` instead of
+/// aborting if the output can't be allocated. Useful when decoding
+/// untrusted input of arbitrary size.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, try_decode};
+///
+/// assert_eq!(try_decode("zCn8eVZg").unwrap(), (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn try_decode<T: AsRef<str>>(data: T) -> Result<Decoded> {
+    let data = data.as_ref();
+    let (base, _) = split(data)?;
+    let mut decoded = try_buffer(base.max_decoded_len(data.len()))?;
+
+    let (base, written) = data.decode_slice(&mut decoded)?;
+    decoded.truncate(written);
+
+    Ok(Decoded { base, data: decoded })
+}
@@ -0,0 +1,172 @@
+//! Stream adapters for [futures](https://docs.rs/futures), encoding or
+//! decoding each chunk of a stream as it comes in.
+
+#[cfg(feature = "encode")]
+use std::io::Write;
+#[cfg(feature = "encode")]
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(feature = "decode")]
+use bytes::Bytes;
+use futures_core::Stream;
+
+#[cfg(feature = "encode")]
+use {Base, EncodeWriter};
+#[cfg(feature = "decode")]
+use {Decoder, Result};
+
+/// Encoded text chunks. Returned by [`encode_stream`](fn.encode_stream.html).
+#[cfg(feature = "encode")]
+#[derive(Debug)]
+pub struct EncodeStream<S> {
+    inner: S,
+    encoder: EncodeWriter<Vec<u8>>,
+    done: bool,
+}
+
+/// Encode the chunks of a byte stream with the given base, as if they had
+/// been concatenated, producing the text in chunks as it is encoded. The
+/// first chunk starts with the prefix.
+///
+/// Bitwise bases produce a chunk of text for about every chunk of data.
+/// The others treat the data as one big number, so they produce all of the
+/// text in one chunk once the stream ends.
+///
+/// # Examples
+///
+/// ```
+/// extern crate bytes;
+/// extern crate futures;
+/// extern crate multibase;
+///
+/// use bytes::Bytes;
+/// use futures::executor::block_on_stream;
+/// use futures::stream;
+/// use multibase::{Base, encode_stream};
+///
+/// let chunks = stream::iter(vec![Bytes::from("hel"), Bytes::from("lo")]);
+/// let encoded: String = block_on_stream(encode_stream(Base::Base64pad, chunks)).collect();
+///
+/// assert_eq!(encoded, "MaGVsbG8=");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_stream<S>(base: Base, inner: S) -> EncodeStream<S>
+    where S: Stream + Unpin, S::Item: AsRef<[u8]>
+{
+    EncodeStream {
+        inner,
+        encoder: EncodeWriter::new(base, Vec::new()),
+        done: false,
+    }
+}
+
+#[cfg(feature = "encode")]
+impl<S> Stream for EncodeStream<S>
+    where S: Stream + Unpin, S::Item: AsRef<[u8]>
+{
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<String>> {
+        let this = self.get_mut();
+
+        while !this.done {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(chunk)) => {
+                    this.encoder.write_all(chunk.as_ref()).expect("writing to a Vec never fails");
+                }
+                Poll::Ready(None) => {
+                    this.encoder.write_tail().expect("writing to a Vec never fails");
+                    this.done = true;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+
+            let encoded = mem::take(this.encoder.get_mut());
+
+            if !encoded.is_empty() {
+                // Prefixes are chars and alphabets are ASCII.
+                return Poll::Ready(Some(unsafe { String::from_utf8_unchecked(encoded) }));
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
+
+/// Decoded byte chunks. Returned by [`decode_stream`](fn.decode_stream.html).
+#[cfg(feature = "decode")]
+#[derive(Debug)]
+pub struct DecodeStream<S> {
+    inner: S,
+    decoder: Decoder,
+    done: bool,
+}
+
+/// Decode the chunks of a stream of multibase text, as if they had been
+/// concatenated, producing the data in chunks as it is decoded.
+///
+/// Chunks may split the text anywhere, even inside a character. Bitwise
+/// bases produce a chunk of data for about every chunk of text. The others
+/// treat the data as one big number, so they produce all of it in one
+/// chunk once the stream ends. The stream ends after the first error.
+///
+/// # Examples
+///
+/// ```
+/// extern crate futures;
+/// extern crate multibase;
+///
+/// use futures::executor::block_on_stream;
+/// use futures::stream;
+/// use multibase::decode_stream;
+///
+/// let chunks = stream::iter(vec!["MaGV", "sbG8="]);
+/// let decoded: Vec<_> = block_on_stream(decode_stream(chunks)).collect::<Result<_, _>>().unwrap();
+///
+/// assert_eq!(decoded.concat(), b"hello");
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_stream<S>(inner: S) -> DecodeStream<S>
+    where S: Stream + Unpin, S::Item: AsRef<[u8]>
+{
+    DecodeStream {
+        inner,
+        decoder: Decoder::new(),
+        done: false,
+    }
+}
+
+#[cfg(feature = "decode")]
+impl<S> Stream for DecodeStream<S>
+    where S: Stream + Unpin, S::Item: AsRef<[u8]>
+{
+    type Item = Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<Bytes>>> {
+        let this = self.get_mut();
+
+        while !this.done {
+            let result = match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(chunk)) => this.decoder.push_bytes(chunk.as_ref(), false),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    this.decoder.push_bytes(&[], true)
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if let Err(err) = result {
+                this.done = true;
+                return Poll::Ready(Some(Err(err)));
+            }
+
+            if !this.decoder.decoded().is_empty() {
+                return Poll::Ready(Some(Ok(Bytes::copy_from_slice(this.decoder.decoded()))));
+            }
+        }
+
+        Poll::Ready(None)
+    }
+}
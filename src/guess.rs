@@ -0,0 +1,53 @@
+//! Heuristic detection of the base of strings that carry no multibase
+//! prefix, such as legacy Base58 or Base64 data.
+//!
+//! A string can be valid in several bases at once, so the result is a
+//! list of candidates rather than a single answer.
+//!
+//! # Examples
+//!
+//! ```
+//! use multibase::{Base, guess};
+//!
+//! assert_eq!(guess::candidates("deadbeef")[0], Base::Base16);
+//! assert_eq!(guess::candidates("aGVsbG8="), [Base::Base64urlpad, Base::Base64pad]);
+//! ```
+
+use alloc::vec::Vec;
+
+use Base;
+
+/// Bases the string decodes in, most likely first.
+///
+/// A base is a candidate when every character is in its alphabet and the
+/// length and padding are valid for it. Bases with smaller alphabets rank
+/// higher, since data in a large alphabet rarely sticks to a small subset
+/// of it by chance. Among alphabets of the same size, bases further along
+/// in the spec rank higher. An empty string yields no candidates.
+pub fn candidates(data: &str) -> Vec<Base> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<Base> = Base::ALL.iter()
+        .cloned()
+        .filter(|base| base.validate_raw(data).is_ok())
+        .collect();
+
+    candidates.sort_by_key(|base| (base.alphabet().len(), base.status() as u8));
+    candidates
+}
+
+/// The most likely base of the string, if it decodes in any.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, guess};
+///
+/// assert_eq!(guess::best("Cn8eVZg"), Some(Base::Base58btc));
+/// assert_eq!(guess::best("not base64!"), None);
+/// ```
+pub fn best(data: &str) -> Option<Base> {
+    candidates(data).first().cloned()
+}
@@ -0,0 +1,60 @@
+//! Fixed-capacity output via the [heapless](https://docs.rs/heapless)
+//! crate, for targets without an allocator.
+
+#[cfg(feature = "encode")]
+use heapless::String;
+use heapless::Vec;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(feature = "decode")]
+use Decodable;
+use {Base, Result};
+
+/// Encode with the given base into a `heapless::String` with capacity `N`.
+/// Fails with `Error::BufferTooSmall` if the output doesn't fit.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, encode_heapless};
+///
+/// assert_eq!(encode_heapless::<8, _>(Base::Base58btc, b"hello").unwrap(),
+///            "zCn8eVZg");
+/// assert_eq!(encode_heapless::<7, _>(Base::Base58btc, b"hello"),
+///            Err(Error::BufferTooSmall));
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_heapless<const N: usize, T: Encodable>(base: Base, data: T) -> Result<String<N>> {
+    let mut encoded = Vec::<u8, N>::new();
+    encoded.resize_default(N).expect("resizing to the capacity always fits");
+
+    let written = data.encode_slice(base, &mut encoded)?;
+    encoded.truncate(written);
+
+    // Prefixes are chars and alphabets are ASCII.
+    Ok(unsafe { String::from_utf8_unchecked(encoded) })
+}
+
+/// Decode the string into a `heapless::Vec` with capacity `N`. Fails with
+/// `Error::BufferTooSmall` if the output doesn't fit.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_heapless};
+///
+/// let (base, data) = decode_heapless::<16, _>("zCn8eVZg").unwrap();
+///
+/// assert_eq!(base, Base::Base58btc);
+/// assert_eq!(data, b"hello");
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_heapless<const N: usize, T: Decodable>(data: T) -> Result<(Base, Vec<u8, N>)> {
+    let mut decoded = Vec::new();
+    decoded.resize_default(N).expect("resizing to the capacity always fits");
+
+    let (base, written) = data.decode_slice(&mut decoded)?;
+    decoded.truncate(written);
+
+    Ok((base, decoded))
+}
@@ -0,0 +1,116 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str;
+
+use {Base, Encodable, Result};
+
+/// Encoded text held on the stack, for formatting IDs and keys without
+/// allocating. Returned by [`encode_inline`](fn.encode_inline.html).
+///
+/// Derefs to `str`, and compares, hashes and formats like one.
+#[derive(Clone)]
+pub struct InlineString {
+    len: u8,
+    buf: [u8; InlineString::CAPACITY],
+}
+
+impl InlineString {
+    /// Most bytes the string can hold, prefix included: enough for a
+    /// 64-byte key in base32 or an 80-byte one in base58btc.
+    pub const CAPACITY: usize = 128;
+
+    /// Get the text.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Prefixes are chars and alphabets are ASCII.
+        unsafe { str::from_utf8_unchecked(&self.buf[..self.len as usize]) }
+    }
+}
+
+/// Encode with the given base into an `InlineString`, without allocating.
+/// Fails with `Error::BufferTooSmall` if the output is longer than
+/// `InlineString::CAPACITY` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, encode_inline};
+///
+/// assert_eq!(encode_inline(Base::Base58btc, b"hello").unwrap(), "zCn8eVZg");
+/// assert_eq!(encode_inline(Base::Base16, [0; 64]),
+///            Err(Error::BufferTooSmall));
+/// ```
+pub fn encode_inline<T: Encodable>(base: Base, data: T) -> Result<InlineString> {
+    let mut buf = [0; InlineString::CAPACITY];
+    let len = data.encode_slice(base, &mut buf)?;
+
+    Ok(InlineString { len: len as u8, buf })
+}
+
+impl Deref for InlineString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for InlineString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for InlineString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for InlineString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for InlineString {
+    fn eq(&self, other: &InlineString) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for InlineString {}
+
+impl PartialEq<str> for InlineString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for InlineString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for InlineString {
+    fn partial_cmp(&self, other: &InlineString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InlineString {
+    fn cmp(&self, other: &InlineString) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for InlineString {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
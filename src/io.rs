@@ -0,0 +1,318 @@
+use std::io::{self, Read, Write};
+#[cfg(feature = "decode")]
+use std::cmp;
+#[cfg(feature = "encode")]
+use std::mem;
+
+#[cfg(feature = "encode")]
+use rfc4648;
+#[cfg(feature = "decode")]
+use Decoder;
+use Base;
+
+/// Number of bytes handled at a time by bitwise bases.
+pub(crate) const CHUNK: usize = 3 * 1024;
+
+/// Encode bytes written to it with the given base, writing the multibase
+/// text to an inner writer.
+///
+/// [Bitwise](enum.Base.html#method.is_bitwise) bases are encoded in
+/// fixed-size chunks as the data comes in, so arbitrarily large inputs
+/// stream through in constant memory. The other bases treat the data as
+/// one big number and have to buffer all of it until `finish`.
+///
+/// Call `finish` once everything has been written: it encodes whatever is
+/// left, padding included, and hands back the inner writer. Dropping the
+/// adapter without finishing loses the tail of the output. After an error
+/// the output is incomplete and the adapter should be discarded.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+/// use multibase::{Base, EncodeWriter};
+///
+/// let mut writer = EncodeWriter::new(Base::Base64pad, Vec::new());
+///
+/// writer.write_all(b"hel").unwrap();
+/// writer.write_all(b"lo").unwrap();
+///
+/// assert_eq!(writer.finish().unwrap(), b"MaGVsbG8=");
+/// ```
+#[cfg(feature = "encode")]
+#[derive(Debug)]
+pub struct EncodeWriter<W: Write> {
+    base: Base,
+    inner: W,
+    pending: Vec<u8>,
+    encoded: Vec<u8>,
+    prefixed: bool,
+}
+
+#[cfg(feature = "encode")]
+impl<W: Write> EncodeWriter<W> {
+    /// Wrap a writer, encoding with the given base.
+    pub fn new(base: Base, inner: W) -> EncodeWriter<W> {
+        EncodeWriter {
+            base,
+            inner,
+            pending: Vec::new(),
+            encoded: Vec::new(),
+            prefixed: false,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner writer. Writing to it directly
+    /// corrupts the output.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Encode the rest of the data, flush, and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.write_tail()?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    /// Encode the rest of the data, without flushing.
+    pub(crate) fn write_tail(&mut self) -> io::Result<()> {
+        let pending = mem::take(&mut self.pending);
+
+        self.write_encoded(&pending)
+    }
+
+    /// Encode `data` and write it out, after the prefix if it hasn't been
+    /// written yet.
+    fn write_encoded(&mut self, data: &[u8]) -> io::Result<()> {
+        if !self.prefixed {
+            let mut prefix = [0; 4];
+
+            self.inner.write_all(self.base.code().encode_utf8(&mut prefix).as_bytes())?;
+            self.prefixed = true;
+        }
+
+        self.encoded.resize(self.base.max_raw_encoded_len(data.len()), 0);
+
+        let written = self.base.encode_raw_slice(data, &mut self.encoded)
+            .expect("buffer is sized to the maximum encoded length");
+
+        self.inner.write_all(&self.encoded[..written])
+    }
+}
+
+#[cfg(feature = "encode")]
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if !self.base.is_bitwise() {
+            self.pending.extend_from_slice(data);
+            return Ok(data.len());
+        }
+
+        let block = rfc4648::block_bytes(self.base.alphabet());
+        let available = self.pending.len() + data.len();
+
+        if available < block {
+            self.pending.extend_from_slice(data);
+            return Ok(data.len());
+        }
+
+        // Only whole blocks encode the same as they would in the full
+        // input, so the tail is kept back for the next write.
+        let taken = available.min(CHUNK) / block * block - self.pending.len();
+        let mut pending = mem::take(&mut self.pending);
+
+        pending.extend_from_slice(&data[..taken]);
+        let result = self.write_encoded(&pending);
+
+        pending.clear();
+        self.pending = pending;
+
+        result.map(|_| taken)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Decode multibase text read from an inner reader, reading the decoded
+/// bytes out of it.
+///
+/// The base is detected from the prefix in the first bytes of the text.
+/// [Bitwise](enum.Base.html#method.is_bitwise) bases are decoded in
+/// fixed-size chunks as the text comes in, in constant memory; the others
+/// treat the data as one big number and have to read all of it before
+/// anything is returned.
+///
+/// Invalid text fails the read with `io::ErrorKind::InvalidData`, wrapping
+/// the `Error`. Positions in it are byte offsets into the whole text.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+/// use multibase::{Base, DecodeReader};
+///
+/// let mut reader = DecodeReader::new(&b"MaGVsbG8="[..]);
+/// let mut decoded = Vec::new();
+///
+/// reader.read_to_end(&mut decoded).unwrap();
+///
+/// assert_eq!(reader.base(), Some(Base::Base64pad));
+/// assert_eq!(decoded, b"hello");
+/// ```
+#[cfg(feature = "decode")]
+#[derive(Debug)]
+pub struct DecodeReader<R: Read> {
+    inner: R,
+    decoder: Decoder,
+    read: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "decode")]
+impl<R: Read> DecodeReader<R> {
+    /// Wrap a reader of multibase text.
+    pub fn new(inner: R) -> DecodeReader<R> {
+        DecodeReader {
+            inner,
+            decoder: Decoder::new(),
+            read: 0,
+            eof: false,
+        }
+    }
+
+    /// Get the base of the text, once its prefix has been read.
+    pub fn base(&self) -> Option<Base> {
+        self.decoder.base()
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Read more text and decode as much of it as can be, returning whether
+    /// there is anything left to do.
+    fn fill(&mut self) -> io::Result<bool> {
+        if self.eof {
+            return Ok(false);
+        }
+
+        let mut text = [0; CHUNK];
+        let read = self.inner.read(&mut text)?;
+
+        self.eof = read == 0;
+        self.read = 0;
+
+        self.decoder.push_bytes(&text[..read], self.eof)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "decode")]
+impl<R: Read> Read for DecodeReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.read == self.decoder.decoded().len() {
+            if !self.fill()? {
+                return Ok(0);
+            }
+        }
+
+        let decoded = &self.decoder.decoded()[self.read..];
+        let len = cmp::min(buf.len(), decoded.len());
+
+        buf[..len].copy_from_slice(&decoded[..len]);
+        self.read += len;
+
+        Ok(len)
+    }
+}
+
+/// Encode everything read from `reader` with the given base, writing the
+/// multibase text to `writer`. Returns the number of bytes of data read.
+///
+/// Data goes through an `EncodeWriter`, so bitwise bases are streamed in
+/// constant memory.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, copy_encode};
+///
+/// let mut encoded = Vec::new();
+///
+/// assert_eq!(copy_encode(&mut &b"hello"[..], &mut encoded, Base::Base64pad).unwrap(), 5);
+/// assert_eq!(encoded, b"MaGVsbG8=");
+/// ```
+#[cfg(feature = "encode")]
+pub fn copy_encode<R, W>(reader: &mut R, writer: &mut W, base: Base) -> io::Result<u64>
+    where R: Read + ?Sized, W: Write + ?Sized
+{
+    let mut encoder = EncodeWriter::new(base, writer);
+    let read = io::copy(reader, &mut encoder)?;
+
+    encoder.finish()?;
+    Ok(read)
+}
+
+/// Decode the multibase text read from `reader`, writing the data to
+/// `writer`. Returns the base and the number of bytes of data written.
+///
+/// Text goes through a `DecodeReader`, so bitwise bases are streamed in
+/// constant memory. Invalid text fails with `io::ErrorKind::InvalidData`,
+/// wrapping the `Error`; data decoded before the failure may already have
+/// been written.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, copy_decode};
+///
+/// let mut decoded = Vec::new();
+///
+/// assert_eq!(copy_decode(&mut &b"zCn8eVZg"[..], &mut decoded).unwrap(), (Base::Base58btc, 5));
+/// assert_eq!(decoded, b"hello");
+/// ```
+#[cfg(feature = "decode")]
+pub fn copy_decode<R, W>(reader: &mut R, writer: &mut W) -> io::Result<(Base, u64)>
+    where R: Read + ?Sized, W: Write + ?Sized
+{
+    let mut decoder = DecodeReader::new(reader);
+    let written = io::copy(&mut decoder, writer)?;
+    let base = decoder.base().expect("the prefix is read before the end of the text");
+
+    Ok((base, written))
+}
+
+/// Encode everything read from `reader` with the given base.
+///
+/// This is `encode` for `Read` sources such as files and sockets, without
+/// a read-to-end step first. Bitwise bases are encoded a chunk at a time,
+/// so only the output is held in memory. `Encodable` can't cover readers,
+/// as reading takes `&mut` and can fail.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_reader};
+///
+/// assert_eq!(encode_reader(Base::Base58btc, &mut &b"hello"[..]).unwrap(), "zCn8eVZg");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_reader<R: Read + ?Sized>(base: Base, reader: &mut R) -> io::Result<String> {
+    let mut encoded = Vec::new();
+    copy_encode(reader, &mut encoded, base)?;
+
+    // Prefixes are chars and alphabets are ASCII.
+    Ok(unsafe { String::from_utf8_unchecked(encoded) })
+}
@@ -0,0 +1,335 @@
+#[cfg(feature = "decode")]
+use alloc::string::String;
+use alloc::vec;
+#[cfg(feature = "encode")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "decode")]
+use lookup::{Lookup, INVALID};
+use {rfc4648, Base};
+#[cfg(feature = "decode")]
+use {DecodeError, Error, Result};
+
+/// Lazily encoded characters, prefix included. Returned by
+/// [`encode_chars`](fn.encode_chars.html).
+#[cfg(feature = "encode")]
+#[derive(Clone, Debug)]
+pub struct EncodeChars<I> {
+    base: Base,
+    data: I,
+    prefixed: bool,
+    buffer: u32,
+    buffered: u32,
+    chars: usize,
+    padding: usize,
+    done: bool,
+    encoded: Option<vec::IntoIter<u8>>,
+}
+
+/// Encode bytes produced by an iterator with the given base, producing the
+/// characters as they are asked for instead of building a `String`.
+///
+/// Bitwise bases pull only as many bytes as the next character needs. The
+/// others treat the data as one big number, so they collect all of it
+/// before the first character after the prefix.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_chars};
+///
+/// let mut chars = encode_chars(Base::Base64pad, b"hello".iter().cloned());
+///
+/// assert_eq!(chars.next(), Some('M'));
+/// assert_eq!(chars.next(), Some('a'));
+/// assert_eq!(chars.collect::<String>(), "GVsbG8=");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_chars<I: IntoIterator<Item = u8>>(base: Base, data: I) -> EncodeChars<I::IntoIter> {
+    EncodeChars {
+        base,
+        data: data.into_iter(),
+        prefixed: false,
+        buffer: 0,
+        buffered: 0,
+        chars: 0,
+        padding: 0,
+        done: false,
+        encoded: None,
+    }
+}
+
+#[cfg(feature = "encode")]
+impl<I: Iterator<Item = u8>> Iterator for EncodeChars<I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        if !self.prefixed {
+            self.prefixed = true;
+            return Some(self.base.code());
+        }
+
+        if !self.base.is_bitwise() {
+            let (base, data) = (self.base, &mut self.data);
+            let encoded = self.encoded
+                .get_or_insert_with(|| base.encode_raw(data.collect::<Vec<u8>>()).into_bytes().into_iter());
+
+            return encoded.next().map(char::from);
+        }
+
+        let alphabet = self.base.alphabet();
+        let bits = alphabet.len().trailing_zeros();
+
+        while self.buffered < bits && !self.done {
+            match self.data.next() {
+                Some(byte) => {
+                    self.buffer = (self.buffer << 8) | u32::from(byte);
+                    self.buffered += 8;
+                }
+                None => {
+                    self.done = true;
+
+                    // The last character is filled up with zero bits.
+                    if self.buffered > 0 {
+                        self.buffer <<= bits - self.buffered;
+                        self.buffered = bits;
+                    }
+
+                    if self.base.is_padded() {
+                        let chars = self.chars + (self.buffered / bits) as usize;
+                        self.padding = rfc4648::missing_padding(alphabet, chars);
+                    }
+                }
+            }
+        }
+
+        if self.buffered >= bits {
+            self.buffered -= bits;
+            self.chars += 1;
+
+            let value = self.buffer >> self.buffered;
+            self.buffer &= (1 << self.buffered) - 1;
+
+            return Some(char::from(alphabet[value as usize]));
+        }
+
+        if self.padding > 0 {
+            self.padding -= 1;
+            return Some(char::from(rfc4648::PADDING));
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let prefix = if self.prefixed { 0 } else { 1 };
+
+        if let Some(ref encoded) = self.encoded {
+            return encoded.size_hint();
+        }
+
+        if !self.base.is_bitwise() {
+            return (prefix, None);
+        }
+
+        let alphabet = self.base.alphabet();
+        let bits = alphabet.len().trailing_zeros() as usize;
+
+        if self.done {
+            let remaining = self.buffered as usize / bits + self.padding;
+            return (prefix + remaining, Some(prefix + remaining));
+        }
+
+        // Every byte read so far went into the characters produced or the
+        // bits still buffered.
+        let read = (self.chars * bits + self.buffered as usize) / 8;
+        let remaining = |len: usize| {
+            rfc4648::encoded_len(alphabet, self.base.is_padded(), read + len) - self.chars + prefix
+        };
+
+        let (lower, upper) = self.data.size_hint();
+        (remaining(lower), upper.map(remaining))
+    }
+}
+
+/// Lazily decoded bytes. Returned by [`decode_chars`](fn.decode_chars.html).
+#[cfg(feature = "decode")]
+#[derive(Clone, Debug)]
+pub struct DecodeChars<I> {
+    data: I,
+    base: Option<Base>,
+    lookup: Lookup,
+    position: usize,
+    buffer: u32,
+    buffered: u32,
+    padding: Option<usize>,
+    done: bool,
+    decoded: Option<vec::IntoIter<u8>>,
+}
+
+/// Decode multibase text produced by an iterator of characters, producing
+/// the bytes as they are asked for instead of building a `Vec`.
+///
+/// Bitwise bases pull only as many characters as the next byte needs, so
+/// a failure is reported as the item where it is found, after the bytes
+/// before it. The others treat the data as one big number, so they
+/// collect all of it before the first byte. Error positions are byte
+/// offsets into the text, and the iterator stops after the first error.
+/// The length of padded text can only be checked at its end, so text with
+/// several problems may fail on a different one than with `decode`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{decode_chars, DecodeError, Error};
+///
+/// let decoded: Result<Vec<u8>, Error> = decode_chars("MaGVsbG8=".chars()).collect();
+/// assert_eq!(decoded.unwrap(), b"hello");
+///
+/// let mut decoded = decode_chars("MaG!".chars());
+/// assert_eq!(decoded.next(), Some(Ok(b'h')));
+/// assert_eq!(decoded.next(), Some(Err(Error::InvalidBaseString(
+///     DecodeError::InvalidCharacter { character: '!', position: 3 }))));
+/// assert_eq!(decoded.next(), None);
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_chars<I: IntoIterator<Item = char>>(data: I) -> DecodeChars<I::IntoIter> {
+    DecodeChars {
+        data: data.into_iter(),
+        base: None,
+        lookup: Lookup::NONE,
+        position: 0,
+        buffer: 0,
+        buffered: 0,
+        padding: None,
+        done: false,
+        decoded: None,
+    }
+}
+
+#[cfg(feature = "decode")]
+impl<I: Iterator<Item = char>> DecodeChars<I> {
+    /// Get the base of the text, once its prefix has been read.
+    pub fn base(&self) -> Option<Base> {
+        self.base
+    }
+
+    /// Read the prefix, and for bases that aren't bitwise, decode the rest
+    /// of the text in one go.
+    fn start(&mut self) -> Result<Base> {
+        let code = self.data.next().ok_or(Error::EmptyInput)?;
+        let base = Base::from_code(code)?;
+
+        self.position = code.len_utf8();
+        self.base = Some(base);
+
+        if base.is_bitwise() {
+            self.lookup = base.lookup();
+        } else {
+            let text: String = self.data.by_ref().collect();
+            let decoded = base.decode_raw(text).map_err(|err| err.offset(self.position))?;
+
+            self.decoded = Some(decoded.into_iter());
+        }
+
+        Ok(base)
+    }
+
+    /// Decode the next byte of a bitwise base.
+    fn next_bitwise(&mut self, base: Base) -> Result<Option<u8>> {
+        let alphabet = base.alphabet();
+        let bits = alphabet.len().trailing_zeros();
+
+        while self.buffered < 8 {
+            let c = match self.data.next() {
+                Some(c) => c,
+                None => return self.end(base).map(|_| None),
+            };
+
+            let position = self.position;
+            self.position += c.len_utf8();
+
+            if base.is_padded() && c == char::from(rfc4648::PADDING) {
+                let start = *self.padding.get_or_insert(position);
+
+                if position - start >= rfc4648::block_chars(alphabet) - 1 {
+                    return Err(DecodeError::InvalidPadding { position: start }.into());
+                }
+
+                continue;
+            }
+
+            if let Some(start) = self.padding {
+                return Err(DecodeError::InvalidPadding { position: start }.into());
+            }
+
+            let value = match self.lookup.get(c as u8) {
+                value if c.is_ascii() && value != INVALID => value,
+                _ => return Err(DecodeError::InvalidCharacter { character: c, position }.into()),
+            };
+
+            self.buffer = (self.buffer << bits) | u32::from(value);
+            self.buffered += bits;
+        }
+
+        self.buffered -= 8;
+
+        let byte = (self.buffer >> self.buffered) as u8;
+        self.buffer &= (1 << self.buffered) - 1;
+
+        Ok(Some(byte))
+    }
+
+    /// Check the end of the text of a bitwise base.
+    fn end(&self, base: Base) -> Result<()> {
+        let alphabet = base.alphabet();
+
+        if base.is_padded() && rfc4648::missing_padding(alphabet, self.position - base.code().len_utf8()) > 0 {
+            return Err(DecodeError::InvalidPadding { position: self.position }.into());
+        }
+
+        if self.buffered >= alphabet.len().trailing_zeros() {
+            return Err(Error::InvalidLength);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "decode")]
+impl<I: Iterator<Item = char>> Iterator for DecodeChars<I> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Result<u8>> {
+        if self.done {
+            return None;
+        }
+
+        let base = match self.base {
+            Some(base) => base,
+            None => match self.start() {
+                Ok(base) => base,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            },
+        };
+
+        if let Some(ref mut decoded) = self.decoded {
+            return decoded.next().map(Ok);
+        }
+
+        match self.next_bitwise(base) {
+            Ok(Some(byte)) => Some(Ok(byte)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! [JSON Schema](https://json-schema.org) support via
+//! [schemars](https://docs.rs/schemars), for documenting multibase fields.
+
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use {Base, MultibaseStr, MultibaseString};
+
+/// A regular expression matching multibase strings in any of the bases
+/// this build supports: the code of a base followed by characters of its
+/// alphabet, and padding for padded bases.
+fn pattern() -> String {
+    let mut pattern = String::from("^(?:");
+
+    for (i, base) in Base::ALL.iter().enumerate() {
+        if i > 0 {
+            pattern.push('|');
+        }
+
+        let code = base.code();
+
+        if !code.is_alphanumeric() {
+            pattern.push('\\');
+        }
+
+        pattern.push(code);
+        pattern.push('[');
+
+        let mut alphabet = base.alphabet().to_vec();
+        alphabet.sort_unstable();
+
+        // Collapse runs of consecutive characters into ranges.
+        let mut i = 0;
+
+        while i < alphabet.len() {
+            let mut end = i;
+
+            while end + 1 < alphabet.len() && alphabet[end + 1] == alphabet[end] + 1 {
+                end += 1;
+            }
+
+            push_class_char(&mut pattern, alphabet[i]);
+
+            if end > i + 1 {
+                pattern.push('-');
+            }
+
+            if end > i {
+                push_class_char(&mut pattern, alphabet[end]);
+            }
+
+            i = end + 1;
+        }
+
+        pattern.push_str("]*");
+
+        if base.is_padded() {
+            pattern.push_str("=*");
+        }
+    }
+
+    pattern.push_str(")$");
+    pattern
+}
+
+/// Append a character to a character class, escaping it if needed.
+fn push_class_char(pattern: &mut String, c: u8) {
+    if let b'\\' | b']' | b'[' | b'-' | b'^' = c {
+        pattern.push('\\');
+    }
+
+    pattern.push(c as char);
+}
+
+/// A string schema with a pattern that only matches the bases this build
+/// supports.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate schemars;
+/// use multibase::MultibaseString;
+///
+/// let schema = schemars::schema_for!(MultibaseString);
+///
+/// assert_eq!(schema.get("type").unwrap(), "string");
+/// ```
+impl JsonSchema for MultibaseString {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("MultibaseString")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("multibase::MultibaseString")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": pattern(),
+            "description": "A multibase string: a base code followed by data in that base.",
+        })
+    }
+}
+
+impl JsonSchema for MultibaseStr {
+    fn schema_name() -> Cow<'static, str> {
+        MultibaseString::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        MultibaseString::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        MultibaseString::json_schema(generator)
+    }
+}
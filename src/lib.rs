@@ -1,20 +1,265 @@
-/// ! # multibase
-/// !
-/// ! Implementation of [multibase](https://github.com/multiformats/multibase) in Rust.
+//! # multibase
+//!
+//! Implementation of [multibase](https://github.com/multiformats/multibase) in Rust.
 
-extern crate base_x;
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
+// Without `std`, `std::` paths resolve to `core`, which the rest of the
+// crate sticks to outside of the modules that need `alloc` or `std`.
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate alloc;
+
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "futures")]
+extern crate futures_core;
+#[cfg(feature = "check")]
+extern crate sha2;
+#[cfg(feature = "heapless")]
+extern crate heapless;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "serde")]
+extern crate serde as serde_crate;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tokio-codec")]
+extern crate tokio_util;
+
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+use std::str;
+
+#[cfg(not(any(feature = "base2", feature = "base8", feature = "base10", feature = "base16",
+              feature = "base32", feature = "base58", feature = "base64")))]
+compile_error!("enable at least one base feature, such as `base58`");
+
+#[cfg(not(any(feature = "encode", feature = "decode")))]
+compile_error!("enable the `encode` feature, the `decode` feature, or both");
+
+// Defines `for_each_base!`, generated from multibase.csv by build.rs.
+include!(concat!(env!("OUT_DIR"), "/bases.rs"));
+
+#[cfg(feature = "allocator_api")]
+mod allocator;
 mod base;
+#[cfg(feature = "alloc")]
+mod batch;
+mod bignum;
+mod canonical;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+mod config;
+#[cfg(feature = "alloc")]
+mod context;
 mod error;
+#[cfg(feature = "decode")]
 mod decodable;
+#[cfg(feature = "encode")]
+mod display;
+#[cfg(feature = "encode")]
 mod encodable;
+#[cfg(feature = "alloc")]
+mod fallible;
+#[cfg(feature = "encode")]
+mod inline;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "alloc")]
+mod iter;
+#[cfg(feature = "decode")]
+mod lookup;
+#[cfg(all(feature = "alloc", feature = "base64"))]
+mod mime;
+mod rfc4648;
+mod simd;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+mod string;
 
+#[cfg(feature = "alloc")]
+pub mod codec;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub mod guess;
+pub mod raw;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(all(feature = "bytes", feature = "decode"))]
+mod bytes_buf;
+#[cfg(feature = "check")]
+mod check;
+#[cfg(feature = "futures")]
+mod futures_stream;
+#[cfg(feature = "heapless")]
+mod heapless_buf;
+#[cfg(all(feature = "schemars", feature = "decode"))]
+mod json_schema;
+#[cfg(feature = "rayon")]
+mod batch_par;
+#[cfg(all(feature = "rayon", feature = "encode"))]
+mod bignum_par;
+#[cfg(feature = "smallvec")]
+mod smallvec_buf;
+#[cfg(feature = "tokio-codec")]
+mod tokio_codec;
+#[cfg(feature = "tokio")]
+mod tokio_io;
+
+#[cfg(feature = "alloc")]
+pub mod base91;
+#[cfg(feature = "alloc")]
+pub mod z85;
+#[cfg(feature = "ascii85")]
+pub mod ascii85;
+
+#[cfg(all(feature = "allocator_api", feature = "encode"))]
+pub use allocator::encode_in;
+#[cfg(all(feature = "allocator_api", feature = "decode"))]
+pub use allocator::decode_in;
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub use batch::encode_many;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use batch::{decode_many, decode_lines, decode_delimited};
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use config::{decode_with, DecodeConfig};
+#[cfg(feature = "alloc")]
+pub use context::Context;
+pub use canonical::NonCanonical;
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+pub use canonical::{normalize, is_canonical, check_canonical};
+#[cfg(feature = "decode")]
 pub use decodable::Decodable;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use decodable::{decode_lenient, decode_permissive, decode_skip_whitespace, Decoded, Lenient};
+#[cfg(feature = "encode")]
+pub use display::{display, Encoded};
+#[cfg(feature = "encode")]
 pub use encodable::Encodable;
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub use fallible::try_encode;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use fallible::try_decode;
+#[cfg(feature = "encode")]
+pub use inline::{encode_inline, InlineString};
+#[cfg(all(feature = "std", feature = "encode"))]
+pub use io::{copy_encode, encode_reader, EncodeWriter};
+#[cfg(all(feature = "std", feature = "decode"))]
+pub use io::{copy_decode, DecodeReader};
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub use iter::{encode_chars, EncodeChars};
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use iter::{decode_chars, DecodeChars};
+#[cfg(all(feature = "std", feature = "encode"))]
+pub use stream::Encoder;
+#[cfg(all(feature = "std", feature = "decode"))]
+pub use stream::Decoder;
+#[cfg(all(feature = "alloc", feature = "base64", feature = "encode"))]
+pub use mime::encode_mime;
+#[cfg(all(feature = "alloc", feature = "base64", feature = "decode"))]
+pub use mime::decode_mime;
 
-pub use base::Base;
+pub use base::{Base, Status};
 pub use Base::*;
-pub use error::{Error, Result};
+pub use error::{DecodeError, Error, Result};
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub use string::{MultibaseStr, MultibaseString};
+#[cfg(all(feature = "bytes", feature = "decode"))]
+pub use bytes_buf::{decode_bytes, decode_into_bytes_mut};
+#[cfg(all(feature = "check", feature = "encode"))]
+pub use check::encode_check;
+#[cfg(all(feature = "check", feature = "decode"))]
+pub use check::decode_check;
+#[cfg(all(feature = "futures", feature = "encode"))]
+pub use futures_stream::{encode_stream, EncodeStream};
+#[cfg(all(feature = "futures", feature = "decode"))]
+pub use futures_stream::{decode_stream, DecodeStream};
+#[cfg(all(feature = "heapless", feature = "encode"))]
+pub use heapless_buf::encode_heapless;
+#[cfg(all(feature = "heapless", feature = "decode"))]
+pub use heapless_buf::decode_heapless;
+#[cfg(all(feature = "rayon", feature = "encode"))]
+pub use batch_par::encode_many_par;
+#[cfg(all(feature = "rayon", feature = "decode"))]
+pub use batch_par::decode_many_par;
+#[cfg(feature = "serde")]
+pub use serde::base_code;
+#[cfg(all(feature = "smallvec", feature = "encode"))]
+pub use smallvec_buf::encode_smallvec;
+#[cfg(all(feature = "smallvec", feature = "decode"))]
+pub use smallvec_buf::decode_smallvec;
+#[cfg(feature = "tokio-codec")]
+pub use tokio_codec::LineCodec;
+#[cfg(all(feature = "tokio", feature = "encode"))]
+pub use tokio_io::AsyncEncodeWriter;
+#[cfg(all(feature = "tokio", feature = "decode"))]
+pub use tokio_io::AsyncDecodeReader;
+
+#[cfg(feature = "alloc")]
+macro_rules! build_base_modules {
+    {$( $(#[$attr:meta])* $val:literal => $var:ident, $module:ident: $name:literal, $alph:literal ($status:ident, $padded:literal), )*} => {
+        $(
+            $(#[$attr])*
+            ///
+            #[doc = concat!("Shorthands for `Base::", stringify!($var), "`, so code that always uses ")]
+            #[doc = concat!(stringify!($module), " can't pass the wrong base.")]
+            pub mod $module {
+                #[cfg(feature = "encode")]
+                use alloc::string::String;
+                #[cfg(feature = "decode")]
+                use alloc::vec::Vec;
+
+                use Base;
+                #[cfg(feature = "decode")]
+                use Result;
+
+                /// Encode, prefix included.
+                #[cfg(feature = "encode")]
+                #[inline]
+                pub fn encode<T: AsRef<[u8]>>(data: T) -> String {
+                    Base::$var.encode(data)
+                }
+
+                /// Encode, leaving out the prefix.
+                #[cfg(feature = "encode")]
+                #[inline]
+                pub fn encode_raw<T: AsRef<[u8]>>(data: T) -> String {
+                    Base::$var.encode_raw(data)
+                }
+
+                /// Decode a string carrying this base's prefix. Strings in any
+                /// other base are rejected with `Error::WrongBase`.
+                #[cfg(feature = "decode")]
+                #[inline]
+                pub fn decode<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+                    ::decode_with_expected_base(data, Base::$var)
+                }
+
+                /// Decode a string that has no prefix.
+                #[cfg(feature = "decode")]
+                #[inline]
+                pub fn decode_raw<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+                    Base::$var.decode_raw(data)
+                }
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "alloc")]
+for_each_base!(build_base_modules);
 
 /// Decode the string.
 ///
@@ -23,15 +268,314 @@ pub use error::{Error, Result};
 /// ```
 /// use multibase::{Base, decode};
 ///
-/// assert_eq!(decode("zCn8eVZg").unwrap(),
+/// let decoded = decode("zCn8eVZg").unwrap();
+///
+/// assert_eq!(decoded.base, Base::Base58btc);
+/// assert_eq!(decoded.data, b"hello");
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+#[inline]
+pub fn decode<T: Decodable>(data: T) -> Result<Decoded> {
+    data.decode()
+}
+
+/// Decode a string slice.
+///
+/// The prefix is read as a single Unicode scalar value, so multibyte codes
+/// are handled without splitting a character. Being a `str`, the input is
+/// valid UTF-8 by construction.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode_str};
+///
+/// assert_eq!(decode_str("zCn8eVZg").unwrap(),
 ///            (Base::Base58btc, b"hello".to_vec()));
+/// assert_eq!(decode_str(""), Err(Error::EmptyInput));
 /// ```
-    #[inline]
-pub fn decode<T: Decodable>(data: T) -> Result<(Base, Vec<u8>)> {
+#[cfg(all(feature = "alloc", feature = "decode"))]
+#[inline]
+pub fn decode_str(data: &str) -> Result<Decoded> {
     data.decode()
 }
 
-/// Encode with the given string
+/// Decode the string, appending the raw binary data to a caller-supplied
+/// buffer so it can be reused across calls. Returns the base and the number
+/// of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_into};
+///
+/// let mut buffer = Vec::new();
+///
+/// assert_eq!(decode_into("zCn8eVZg", &mut buffer).unwrap(),
+///            (Base::Base58btc, 5));
+/// assert_eq!(buffer, b"hello");
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+#[inline]
+pub fn decode_into<T: Decodable>(data: T, out: &mut Vec<u8>) -> Result<(Base, usize)> {
+    data.decode_into(out)
+}
+
+/// Decode text produced by an iterator of characters, such as a parser,
+/// a decompressor or a rope, without collecting it into a string first.
+///
+/// Bitwise bases are decoded as the characters come in; see
+/// [`decode_chars`](fn.decode_chars.html) to get at the bytes lazily too.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_iter};
+///
+/// let text = ["zCn8", "eVZg"];
+///
+/// assert_eq!(decode_iter(text.iter().flat_map(|part| part.chars())).unwrap(),
+///            (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub fn decode_iter<I: IntoIterator<Item = char>>(data: I) -> Result<Decoded> {
+    let mut chars = decode_chars(data);
+    let data = chars.by_ref().collect::<Result<Vec<u8>>>()?;
+    let base = chars.base().expect("the prefix is read before any data");
+
+    Ok(Decoded { base, data })
+}
+
+/// Split the prefix off the string without decoding the payload, so the
+/// base can be checked before paying for a full decode.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_prefix};
+///
+/// assert_eq!(decode_prefix("zCn8eVZg").unwrap(), (Base::Base58btc, "Cn8eVZg"));
+/// ```
+#[cfg(feature = "decode")]
+#[inline]
+pub fn decode_prefix(data: &str) -> Result<(Base, &str)> {
+    decodable::split(data)
+}
+
+/// Decode a multibase string held in a byte buffer in place, replacing it
+/// with the decoded data and returning the base. The contents of `data`
+/// are unspecified on error.
+///
+/// Bitwise bases, whose output is always shorter than their input, are
+/// decoded without any allocation; the others go through a temporary
+/// buffer but still reuse `data` for the result.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_mut};
+///
+/// let mut buffer = b"MaGVsbG8=".to_vec();
+///
+/// assert_eq!(decode_mut(&mut buffer), Ok(Base::Base64pad));
+/// assert_eq!(buffer, b"hello");
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub fn decode_mut(data: &mut Vec<u8>) -> Result<Base> {
+    let (base, start) = {
+        let text = str::from_utf8(data)?;
+        let (base, payload) = decodable::split(text)?;
+
+        (base, text.len() - payload.len())
+    };
+
+    let written = base.decode_raw_in_place(data, start)?;
+    data.truncate(written);
+
+    Ok(base)
+}
+
+/// Decode the string into a fixed buffer, without allocating. Returns the
+/// base and the number of bytes written, or `Error::BufferTooSmall` if the
+/// output doesn't fit.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_slice};
+///
+/// let mut buffer = [0; 8];
+///
+/// assert_eq!(decode_slice("zCn8eVZg", &mut buffer).unwrap(),
+///            (Base::Base58btc, 5));
+/// assert_eq!(&buffer[..5], b"hello");
+/// ```
+#[cfg(feature = "decode")]
+#[inline]
+pub fn decode_slice<T: Decodable>(data: T, out: &mut [u8]) -> Result<(Base, usize)> {
+    data.decode_slice(out)
+}
+
+/// Decode the string into a fixed-size array, failing with
+/// `Error::InvalidLength` unless the data is exactly `N` bytes long.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode_exact};
+///
+/// assert_eq!(decode_exact::<5, _>("zCn8eVZg").unwrap(),
+///            (Base::Base58btc, *b"hello"));
+/// assert_eq!(decode_exact::<4, _>("zCn8eVZg"), Err(Error::InvalidLength));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_exact<const N: usize, T: Decodable>(data: T) -> Result<(Base, [u8; N])> {
+    let mut decoded = [0; N];
+
+    match data.decode_slice(&mut decoded) {
+        Ok((base, len)) if len == N => Ok((base, decoded)),
+        Ok(_) | Err(Error::BufferTooSmall) => Err(Error::InvalidLength),
+        Err(err) => Err(err),
+    }
+}
+
+/// Decode a string that must be in the given base, failing with
+/// `Error::WrongBase` if it carries any other prefix.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode_with_expected_base};
+///
+/// assert_eq!(decode_with_expected_base("zCn8eVZg", Base::Base58btc).unwrap(),
+///            b"hello");
+/// assert_eq!(decode_with_expected_base("zCn8eVZg", Base::Base32),
+///            Err(Error::WrongBase { expected: Base::Base32, found: Base::Base58btc }));
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub fn decode_with_expected_base<T: AsRef<str>>(data: T, expected: Base) -> Result<Vec<u8>> {
+    let data = data.as_ref();
+    let (found, payload) = decodable::split(data)?;
+
+    if found != expected {
+        return Err(Error::WrongBase { expected, found });
+    }
+
+    found.decode_raw(payload).map_err(|err| err.offset(data.len() - payload.len()))
+}
+
+/// Check that the string would decode, without decoding it. The prefix,
+/// every character and the padding are checked, and the base is returned.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, DecodeError, Error, validate};
+///
+/// assert_eq!(validate("zCn8eVZg"), Ok(Base::Base58btc));
+/// assert_eq!(validate("zCn8eVZ0"),
+///            Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '0', position: 7 })));
+/// ```
+#[cfg(feature = "decode")]
+pub fn validate<T: AsRef<str>>(data: T) -> Result<Base> {
+    let data = data.as_ref();
+    let (base, payload) = decodable::split(data)?;
+
+    base.validate_raw(payload).map_err(|err| err.offset(data.len() - payload.len()))?;
+    Ok(base)
+}
+
+/// Whether the string is a valid multibase string.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::is_valid;
+///
+/// assert!(is_valid("MZg=="));
+/// assert!(!is_valid("MZg="));
+/// ```
+#[cfg(feature = "decode")]
+#[inline]
+pub fn is_valid<T: AsRef<str>>(data: T) -> bool {
+    validate(data).is_ok()
+}
+
+/// Decode the string, rejecting bases that are still draft or experimental
+/// in the multibase spec. Drafts are rejected too on purpose: their
+/// alphabets and codes may still change, which protocols that pin down
+/// their accepted bases want to avoid. The base is checked before the
+/// payload is decoded, so rejected input costs nothing to turn down.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, decode_strict_spec};
+///
+/// assert_eq!(decode_strict_spec("zCn8eVZg").unwrap(),
+///            (Base::Base58btc, b"hello".to_vec()));
+/// assert_eq!(decode_strict_spec("hpfsxgidn"), Err(Error::UnstableBase));
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub fn decode_strict_spec<T: AsRef<str>>(data: T) -> Result<Decoded> {
+    let data = data.as_ref();
+    let (base, _) = decodable::split(data)?;
+
+    match base.status() {
+        Status::Default | Status::Candidate => data.decode(),
+        Status::Draft | Status::Experimental => Err(Error::UnstableBase),
+    }
+}
+
+/// Decode the string and re-encode its data with another base. Input that
+/// is already in the target base is returned as is once it has been
+/// checked.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, transcode};
+///
+/// assert_eq!(transcode("zCn8eVZg", Base::Base64pad).unwrap(), "MaGVsbG8=");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode", feature = "decode"))]
+pub fn transcode<T: AsRef<str>>(input: T, to: Base) -> Result<String> {
+    let input = input.as_ref();
+
+    if validate(input)? == to {
+        return Ok(String::from(input));
+    }
+
+    Ok(input.decode()?.encode(to))
+}
+
+/// Whether two multibase strings carry the same data, even if they are
+/// encoded in different bases.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::payload_eq;
+///
+/// assert_eq!(payload_eq("zCn8eVZg", "f68656c6c6f"), Ok(true));
+/// assert_eq!(payload_eq("zCn8eVZg", "f68656c6c"), Ok(false));
+/// ```
+#[cfg(all(feature = "alloc", feature = "decode"))]
+pub fn payload_eq<A: AsRef<str>, B: AsRef<str>>(a: A, b: B) -> Result<bool> {
+    let (a, b) = (a.as_ref(), b.as_ref());
+
+    if a == b {
+        return validate(a).map(|_| true);
+    }
+
+    Ok(a.decode()?.data == b.decode()?.data)
+}
+
+/// Encode with the given base.
+///
+/// The output is always ASCII, so it is returned as a `String` that can be
+/// formatted and compared directly. Every base in the table is implemented,
+/// so encoding cannot fail.
 ///
 /// # Examples
 ///
@@ -41,6 +585,107 @@ pub fn decode<T: Decodable>(data: T) -> Result<(Base, Vec<u8>)> {
 /// assert_eq!(encode(Base::Base58btc, b"hello"),
 ///            "zCn8eVZg");
 /// ```
+#[cfg(all(feature = "alloc", feature = "encode"))]
 pub fn encode<T: Encodable>(base: Base, data: T) -> String {
     data.encode(base)
 }
+
+/// Encode bytes produced by an iterator with the given base.
+///
+/// Byte arrays of any length, slices and vectors are already `Encodable`;
+/// this covers generated data without collecting it by hand first.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_iter};
+///
+/// assert_eq!(encode_iter(Base::Base16, (1..5).map(|i| i * 16)),
+///            "f10203040");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub fn encode_iter<I: IntoIterator<Item = u8>>(base: Base, data: I) -> String {
+    data.into_iter().collect::<Vec<u8>>().encode(base)
+}
+
+/// Encode with the given base, leaving out any trailing padding, for
+/// systems that don't expect it. Decoding the output with a padded base
+/// requires `DecodeConfig::allow_missing_padding`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_without_padding};
+///
+/// assert_eq!(encode_without_padding(Base::Base64pad, b"f"), "MZg");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub fn encode_without_padding<T: Encodable>(base: Base, data: T) -> String {
+    let mut encoded = data.encode(base);
+    let len = encoded.trim_end_matches('=').len();
+
+    encoded.truncate(len);
+    encoded
+}
+
+/// Encode with the given base, appending to a byte buffer. Returns the
+/// number of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_into};
+///
+/// let mut buffer = b"id: ".to_vec();
+///
+/// assert_eq!(encode_into(Base::Base58btc, b"hello", &mut buffer), 8);
+/// assert_eq!(buffer, b"id: zCn8eVZg");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub fn encode_into<T: Encodable>(base: Base, data: T, out: &mut Vec<u8>) -> usize {
+    data.encode_into(base, out)
+}
+
+/// Encode with the given base into a fixed buffer, without allocating.
+/// Returns the number of bytes written, or `Error::BufferTooSmall` if the
+/// output doesn't fit.
+///
+/// Bases that aren't bitwise use `out` past the encoding as scratch space,
+/// up to [`Base::max_encoded_len`](enum.Base.html#method.max_encoded_len),
+/// so those bytes are unspecified even on success.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Error, encode_slice};
+///
+/// let mut buffer = [0; 8];
+///
+/// assert_eq!(encode_slice(Base::Base58btc, b"hello", &mut buffer), Ok(8));
+/// assert_eq!(&buffer, b"zCn8eVZg");
+/// assert_eq!(encode_slice(Base::Base58btc, b"hello", &mut buffer[..7]),
+///            Err(Error::BufferTooSmall));
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_slice<T: Encodable>(base: Base, data: T, out: &mut [u8]) -> Result<usize> {
+    data.encode_slice(base, out)
+}
+
+/// Encode with the given base, appending to a string. Returns the number
+/// of bytes written.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_into_string};
+///
+/// let mut buffer = String::from("id: ");
+///
+/// assert_eq!(encode_into_string(Base::Base58btc, b"hello", &mut buffer), 8);
+/// assert_eq!(buffer, "id: zCn8eVZg");
+/// ```
+#[cfg(all(feature = "alloc", feature = "encode"))]
+pub fn encode_into_string<T: Encodable>(base: Base, data: T, out: &mut String) -> usize {
+    // Only ASCII is ever appended, so the string stays valid UTF-8.
+    data.encode_into(base, unsafe { out.as_mut_vec() })
+}
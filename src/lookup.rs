@@ -0,0 +1,147 @@
+//! Maps from characters back to their values in an alphabet, built at
+//! compile time for every base, see `Base::lookup`.
+//!
+//! By default every base has a full table indexed by byte, so a lookup is
+//! a single load. With the `compact-tables` feature the tables only cover
+//! the characters alphabets are made of, and the uppercase variants of
+//! case-insensitive bases share the table of the lowercase ones, swapping
+//! the case of letters before the lookup.
+
+/// Value of characters that are not in the alphabet.
+pub const INVALID: u8 = 0xff;
+
+/// First character a compact table covers.
+#[cfg(feature = "compact-tables")]
+const FIRST: u8 = b'+';
+
+/// Characters a compact table covers, from `+` to `z`, which holds every
+/// character of every alphabet.
+#[cfg(feature = "compact-tables")]
+const LEN: usize = (b'z' - FIRST) as usize + 1;
+
+#[cfg(not(feature = "compact-tables"))]
+pub type Table = [u8; 256];
+
+#[cfg(feature = "compact-tables")]
+pub type Table = [u8; LEN];
+
+/// Whether the table of an `alphabet` is stored for the other case: true
+/// for alphabets with uppercase letters and no lowercase ones.
+pub const fn swaps_case(alphabet: &[u8]) -> bool {
+    let mut upper = false;
+    let mut i = 0;
+
+    while i < alphabet.len() {
+        if alphabet[i].is_ascii_lowercase() {
+            return false;
+        }
+
+        upper |= alphabet[i].is_ascii_uppercase();
+        i += 1;
+    }
+
+    upper
+}
+
+/// Build the table of an `alphabet`.
+#[cfg(not(feature = "compact-tables"))]
+pub const fn table(alphabet: &[u8]) -> Table {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+
+    while i < alphabet.len() {
+        table[alphabet[i] as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+}
+
+/// Build the table of an `alphabet`, in lowercase if it
+/// [swaps case](fn.swaps_case.html), so that both cases build the same
+/// table and it is stored once.
+#[cfg(feature = "compact-tables")]
+pub const fn table(alphabet: &[u8]) -> Table {
+    let swap = swaps_case(alphabet);
+    let mut table = [INVALID; LEN];
+    let mut i = 0;
+
+    while i < alphabet.len() {
+        let c = alphabet[i];
+        let c = if swap && c.is_ascii_alphabetic() { c ^ 0x20 } else { c };
+
+        assert!(c >= FIRST && c - FIRST < LEN as u8, "alphabet is outside the compact table");
+
+        table[(c - FIRST) as usize] = i as u8;
+        i += 1;
+    }
+
+    table
+}
+
+/// Map from characters to their values in an alphabet, `INVALID` for
+/// characters not in it.
+#[derive(Clone, Copy, Debug)]
+pub struct Lookup {
+    table: &'static Table,
+    #[cfg(feature = "compact-tables")]
+    swap_case: bool,
+}
+
+impl Lookup {
+    /// A lookup with no valid characters.
+    #[cfg(feature = "alloc")]
+    pub const NONE: Lookup = Lookup::new(&table(b""), false);
+
+    /// Wrap the `table` of an alphabet, and whether the alphabet
+    /// [swaps case](fn.swaps_case.html).
+    #[cfg(not(feature = "compact-tables"))]
+    pub const fn new(table: &'static Table, _: bool) -> Lookup {
+        Lookup { table }
+    }
+
+    #[cfg(feature = "compact-tables")]
+    pub const fn new(table: &'static Table, swap_case: bool) -> Lookup {
+        Lookup { table, swap_case }
+    }
+
+    /// Look up the value of a character.
+    #[cfg(not(feature = "compact-tables"))]
+    #[inline(always)]
+    pub fn get(self, c: u8) -> u8 {
+        self.table[c as usize]
+    }
+
+    #[cfg(feature = "compact-tables")]
+    #[inline]
+    pub fn get(self, c: u8) -> u8 {
+        let c = if self.swap_case && c.is_ascii_alphabetic() { c ^ 0x20 } else { c };
+
+        match self.table.get(c.wrapping_sub(FIRST) as usize) {
+            Some(&value) => value,
+            None => INVALID,
+        }
+    }
+
+    /// Call `f` with the table of the ASCII characters, for the vectorized
+    /// kernels.
+    #[cfg(not(feature = "compact-tables"))]
+    #[cfg(any(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"), all(target_arch = "wasm32", target_feature = "simd128")))]
+    #[inline]
+    pub fn with_ascii<R, F: FnOnce(&[u8; 128]) -> R>(self, f: F) -> R {
+        f(self.table.first_chunk().expect("the table covers every byte"))
+    }
+
+    #[cfg(feature = "compact-tables")]
+    #[cfg(any(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"), all(target_arch = "wasm32", target_feature = "simd128")))]
+    #[inline]
+    pub fn with_ascii<R, F: FnOnce(&[u8; 128]) -> R>(self, f: F) -> R {
+        let mut ascii = [INVALID; 128];
+
+        for (c, value) in ascii.iter_mut().enumerate() {
+            *value = self.get(c as u8);
+        }
+
+        f(&ascii)
+    }
+}
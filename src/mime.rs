@@ -0,0 +1,61 @@
+#[cfg(feature = "encode")]
+use alloc::string::String;
+
+#[cfg(feature = "encode")]
+use {Base, Encodable};
+#[cfg(feature = "decode")]
+use {Decodable, Decoded, Result};
+
+/// Maximum line length for MIME bodies, as set by RFC 2045.
+#[cfg(feature = "encode")]
+const LINE_LENGTH: usize = 76;
+
+/// Encode as `Base64pad`, wrapping the output at 76 columns with CRLF line
+/// breaks so it can be embedded in MIME bodies.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::encode_mime;
+///
+/// assert_eq!(encode_mime(b"hello"), "MaGVsbG8=");
+/// assert_eq!(encode_mime([0; 60]).lines().count(), 2);
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_mime<T: AsRef<[u8]>>(data: T) -> String {
+    let encoded = data.as_ref().encode(Base::Base64pad);
+    let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / LINE_LENGTH * 2);
+
+    for (i, line) in encoded.as_bytes().chunks(LINE_LENGTH).enumerate() {
+        if i > 0 {
+            wrapped.push_str("\r\n");
+        }
+
+        // Multibase output is always ASCII, so any split is a char boundary.
+        wrapped.push_str(::std::str::from_utf8(line).expect("multibase output is ASCII"));
+    }
+
+    wrapped
+}
+
+/// Decode the string after stripping any CR/LF line breaks, as produced by
+/// `encode_mime`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_mime};
+///
+/// assert_eq!(decode_mime("MaGV\r\nsbG8=").unwrap(),
+///            (Base::Base64pad, b"hello".to_vec()));
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_mime<T: AsRef<str>>(data: T) -> Result<Decoded> {
+    let data = data.as_ref();
+
+    if data.contains(['\r', '\n']) {
+        data.replace(['\r', '\n'], "").decode()
+    } else {
+        data.decode()
+    }
+}
@@ -0,0 +1,85 @@
+//! Encoding and decoding without the multibase prefix, for callers that
+//! store or transmit the base separately.
+//!
+//! The output of `encode` is exactly what follows the prefix in the
+//! multibase string, and `decode` accepts exactly that. Padded bases are
+//! encoded bit by bit as described in
+//! [RFC 4648](https://tools.ietf.org/html/rfc4648) and require correct
+//! padding when decoding. The other bases treat the data as one big-endian
+//! number, with every leading zero byte encoded as the first character of
+//! the alphabet, as in Base58.
+//!
+//! # Examples
+//!
+//! ```
+//! use multibase::{Base, raw};
+//!
+//! let encoded = raw::encode(Base::Base58btc, b"hello");
+//!
+//! assert_eq!(encoded, "Cn8eVZg");
+//! assert_eq!(raw::decode(Base::Base58btc, &encoded).unwrap(), b"hello");
+//! ```
+
+#[cfg(all(feature = "alloc", feature = "encode"))]
+use alloc::string::String;
+#[cfg(all(feature = "alloc", feature = "decode"))]
+use alloc::vec::Vec;
+
+use {Base, Result};
+
+/// Encode with the given base, leaving out the prefix.
+#[cfg(all(feature = "alloc", feature = "encode"))]
+#[inline]
+pub fn encode<T: AsRef<[u8]>>(base: Base, data: T) -> String {
+    base.encode_raw(data)
+}
+
+/// Decode a string encoded with the given base that has no prefix.
+#[cfg(all(feature = "alloc", feature = "decode"))]
+#[inline]
+pub fn decode<T: AsRef<str>>(base: Base, data: T) -> Result<Vec<u8>> {
+    base.decode_raw(data)
+}
+
+/// Encode with the given base into `out` without allocating. Returns the
+/// number of bytes written, or `Error::BufferTooSmall` if the output
+/// doesn't fit. As with [`encode_slice`](../fn.encode_slice.html), bytes of
+/// `out` past the encoding may be used as scratch space.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, raw};
+///
+/// let mut buffer = [0; 16];
+/// let written = raw::encode_slice(Base::Base64pad, b"f", &mut buffer).unwrap();
+///
+/// assert_eq!(&buffer[..written], b"Zg==");
+/// ```
+#[cfg(feature = "encode")]
+#[inline]
+pub fn encode_slice(base: Base, data: &[u8], out: &mut [u8]) -> Result<usize> {
+    base.encode_raw_slice(data, out)
+}
+
+/// Decode a string that has no prefix into `out` without allocating.
+/// Returns the number of bytes written, or `Error::BufferTooSmall` if the
+/// output doesn't fit.
+#[cfg(feature = "decode")]
+#[inline]
+pub fn decode_slice(base: Base, data: &str, out: &mut [u8]) -> Result<usize> {
+    base.decode_raw_slice(data, out)
+}
+
+/// Maximum length of the encoding of `len` bytes. Exact for the padded
+/// bases, a tight upper bound for the others.
+#[inline]
+pub fn max_encoded_len(base: Base, len: usize) -> usize {
+    base.max_raw_encoded_len(len)
+}
+
+/// Maximum length of the data decoded from `len` characters.
+#[inline]
+pub fn max_decoded_len(base: Base, len: usize) -> usize {
+    base.max_raw_decoded_len(len)
+}
@@ -0,0 +1,326 @@
+//! Bitwise encoding for power-of-two alphabets, as described in
+//! [RFC 4648](https://tools.ietf.org/html/rfc4648).
+
+#[cfg(feature = "decode")]
+use lookup::{Lookup, INVALID};
+use {simd, Error, Result};
+#[cfg(feature = "decode")]
+use DecodeError;
+
+pub const PADDING: u8 = b'=';
+
+/// Number of characters in a full block, which is what padding rounds
+/// the output up to.
+fn block_len(bits: u32) -> usize {
+    let mut len = 1;
+
+    while !(len * bits as usize).is_multiple_of(8) {
+        len += 1;
+    }
+
+    len
+}
+
+/// Number of characters in a full block, so that input split on
+/// multiples of it decodes to the same bytes as the whole.
+#[cfg(all(feature = "decode", feature = "alloc"))]
+pub fn block_chars(alphabet: &[u8]) -> usize {
+    block_len(alphabet.len().trailing_zeros())
+}
+
+/// Number of bytes in a full block, so that input split on multiples of
+/// it encodes to the same characters as the whole.
+#[cfg(feature = "encode")]
+pub fn block_bytes(alphabet: &[u8]) -> usize {
+    let bits = alphabet.len().trailing_zeros();
+
+    block_len(bits) * bits as usize / 8
+}
+
+/// Number of padding characters that complete `len` characters to a full
+/// block.
+#[cfg(feature = "alloc")]
+pub fn missing_padding(alphabet: &[u8], len: usize) -> usize {
+    let block = block_len(alphabet.len().trailing_zeros());
+
+    len.next_multiple_of(block) - len
+}
+
+/// Length of the encoding of `len` bytes.
+pub fn encoded_len(alphabet: &[u8], padded: bool, len: usize) -> usize {
+    let bits = alphabet.len().trailing_zeros() as usize;
+    let chars = (len * 8).div_ceil(bits);
+
+    if padded {
+        chars.next_multiple_of(block_len(bits as u32))
+    } else {
+        chars
+    }
+}
+
+/// Encode the whole blocks at the start of `data` into `out`, a block at a
+/// time, returning the number of bytes read and characters written.
+/// Monomorphized per character width, so the shifts are constants.
+#[cfg(feature = "encode")]
+fn encode_blocks<const BITS: u32>(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let chars = block_len(BITS);
+    let bytes = chars * BITS as usize / 8;
+    let mut blocks = 0;
+
+    for (block, out) in data.chunks_exact(bytes).zip(out.chunks_exact_mut(chars)) {
+        let value = block.iter().fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+
+        for (i, c) in out.iter_mut().enumerate() {
+            let shift = BITS as usize * (chars - 1 - i);
+            *c = alphabet[(value >> shift) as usize & ((1 << BITS) - 1)];
+        }
+
+        blocks += 1;
+    }
+
+    (blocks * bytes, blocks * chars)
+}
+
+/// Decode the whole blocks at the start of `data` into `out`, a block at a
+/// time, returning the number of characters read and bytes written. Stops
+/// at the first block with a character that is not in the alphabet.
+#[cfg(feature = "decode")]
+fn decode_blocks<const BITS: u32>(lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let chars = block_len(BITS);
+    let bytes = chars * BITS as usize / 8;
+    let mut blocks = 0;
+
+    'blocks: for (block, out) in data.chunks_exact(chars).zip(out.chunks_exact_mut(bytes)) {
+        let mut value = 0u64;
+
+        for &c in block {
+            match lookup.get(c) {
+                INVALID => break 'blocks,
+                digit => value = (value << BITS) | u64::from(digit),
+            }
+        }
+
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = (value >> (8 * (bytes - 1 - i))) as u8;
+        }
+
+        blocks += 1;
+    }
+
+    (blocks * chars, blocks * bytes)
+}
+
+/// Encode `data` with a power-of-two `alphabet` into `out`, returning the
+/// number of bytes written.
+#[cfg(feature = "encode")]
+pub fn encode(alphabet: &[u8], padded: bool, data: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = encoded_len(alphabet, padded, data.len());
+    let out = out.get_mut(..len).ok_or(Error::BufferTooSmall)?;
+
+    let bits = alphabet.len().trailing_zeros();
+    let mask = (1 << bits) - 1;
+
+    let (read, mut written) = match bits {
+        1 => encode_blocks::<1>(alphabet, data, out),
+        2 => encode_blocks::<2>(alphabet, data, out),
+        3 => encode_blocks::<3>(alphabet, data, out),
+        4 => {
+            let (read, written) = simd::encode_base16(alphabet, data, out);
+            let (rest, rest_written) = encode_blocks::<4>(alphabet, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        5 => {
+            let (read, written) = simd::encode_base32(alphabet, data, out);
+            let (rest, rest_written) = encode_blocks::<5>(alphabet, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        6 => {
+            let (read, written) = simd::encode_base64(alphabet, data, out);
+            let (rest, rest_written) = encode_blocks::<6>(alphabet, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        7 => encode_blocks::<7>(alphabet, data, out),
+        _ => encode_blocks::<8>(alphabet, data, out),
+    };
+
+    // The tail is less than a block, and handled bit by bit.
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+
+    for &byte in &data[read..] {
+        buffer = (buffer << 8) | u32::from(byte);
+        buffered += 8;
+
+        while buffered >= bits {
+            buffered -= bits;
+            out[written] = alphabet[((buffer >> buffered) & mask) as usize];
+            written += 1;
+        }
+
+        buffer &= (1 << buffered) - 1;
+    }
+
+    if buffered > 0 {
+        out[written] = alphabet[((buffer << (bits - buffered)) & mask) as usize];
+        written += 1;
+    }
+
+    for c in out[written..].iter_mut() {
+        *c = PADDING;
+    }
+
+    Ok(len)
+}
+
+/// Upper bound on the decoding of `len` characters.
+pub fn max_decoded_len(alphabet: &[u8], len: usize) -> usize {
+    len * alphabet.len().trailing_zeros() as usize / 8
+}
+
+/// Check the padding and strip it off `input`.
+#[cfg(feature = "decode")]
+fn strip_padding(bits: u32, padded: bool, input: &[u8]) -> Result<&[u8]> {
+    if !padded {
+        return Ok(input);
+    }
+
+    if !input.len().is_multiple_of(block_len(bits)) {
+        return Err(DecodeError::InvalidPadding { position: input.len() }.into());
+    }
+
+    let trimmed = input.iter().rposition(|&c| c != PADDING).map_or(0, |i| i + 1);
+
+    if input.len() - trimmed >= block_len(bits) {
+        return Err(DecodeError::InvalidPadding { position: trimmed }.into());
+    }
+
+    Ok(&input[..trimmed])
+}
+
+/// Look up the value of the character at `position` of `input`.
+#[cfg(feature = "decode")]
+#[inline]
+fn value(lookup: Lookup, padded: bool, input: &[u8], position: usize) -> Result<u8> {
+    match lookup.get(input[position]) {
+        INVALID if padded && input[position] == PADDING => Err(DecodeError::InvalidPadding { position }.into()),
+        INVALID => Err(Error::invalid_character(input, position)),
+        value => Ok(value),
+    }
+}
+
+/// Decode `input` with a power-of-two `alphabet` into `out`, returning the
+/// number of bytes written.
+#[cfg(feature = "decode")]
+pub fn decode(alphabet: &[u8], lookup: Lookup, padded: bool, input: &str, out: &mut [u8]) -> Result<usize> {
+    let bits = alphabet.len().trailing_zeros();
+    let input = input.as_bytes();
+
+    let data = strip_padding(bits, padded, input)?;
+    let out = out.get_mut(..data.len() * bits as usize / 8).ok_or(Error::BufferTooSmall)?;
+
+    let (read, mut written) = match bits {
+        1 => decode_blocks::<1>(lookup, data, out),
+        2 => decode_blocks::<2>(lookup, data, out),
+        3 => decode_blocks::<3>(lookup, data, out),
+        4 => {
+            let (read, written) = simd::decode_base16(alphabet, lookup, data, out);
+            let (rest, rest_written) = decode_blocks::<4>(lookup, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        5 => {
+            let (read, written) = simd::decode_base32(alphabet, lookup, data, out);
+            let (rest, rest_written) = decode_blocks::<5>(lookup, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        6 => {
+            let (read, written) = simd::decode_base64(alphabet, lookup, data, out);
+            let (rest, rest_written) = decode_blocks::<6>(lookup, &data[read..], &mut out[written..]);
+
+            (read + rest, written + rest_written)
+        }
+        7 => decode_blocks::<7>(lookup, data, out),
+        _ => decode_blocks::<8>(lookup, data, out),
+    };
+
+    // The tail, or the block with an invalid character, is handled bit by
+    // bit.
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+
+    for i in read..data.len() {
+        let value = value(lookup, padded, input, i)?;
+
+        buffer = (buffer << bits) | u32::from(value);
+        buffered += bits;
+
+        if buffered >= 8 {
+            buffered -= 8;
+            out[written] = (buffer >> buffered) as u8;
+            written += 1;
+            buffer &= (1 << buffered) - 1;
+        }
+    }
+
+    if buffered >= bits {
+        return Err(Error::InvalidLength);
+    }
+
+    Ok(written)
+}
+
+/// Decode `buf[start..]` with a power-of-two `alphabet` into the front of
+/// `buf`, returning the number of bytes written. Every byte lands behind
+/// the character that completes it, so nothing is overwritten before it
+/// has been read.
+#[cfg(all(feature = "decode", feature = "alloc"))]
+pub fn decode_in_place(alphabet: &[u8], lookup: Lookup, padded: bool, buf: &mut [u8], start: usize) -> Result<usize> {
+    let bits = alphabet.len().trailing_zeros();
+    let end = start + strip_padding(bits, padded, &buf[start..]).map_err(|err| err.offset(start))?.len();
+    let mut written = 0;
+
+    let mut buffer = 0u32;
+    let mut buffered = 0;
+
+    for i in start..end {
+        let value = value(lookup, padded, buf, i)?;
+
+        buffer = (buffer << bits) | u32::from(value);
+        buffered += bits;
+
+        if buffered >= 8 {
+            buffered -= 8;
+            buf[written] = (buffer >> buffered) as u8;
+            written += 1;
+            buffer &= (1 << buffered) - 1;
+        }
+    }
+
+    if buffered >= bits {
+        return Err(Error::InvalidLength);
+    }
+
+    Ok(written)
+}
+
+/// Check that `input` would decode with a power-of-two `alphabet`, without
+/// decoding it.
+#[cfg(feature = "decode")]
+pub fn validate(alphabet: &[u8], lookup: Lookup, padded: bool, input: &str) -> Result<()> {
+    let bits = alphabet.len().trailing_zeros();
+    let data = strip_padding(bits, padded, input.as_bytes())?;
+
+    for i in 0..data.len() {
+        value(lookup, padded, input.as_bytes(), i)?;
+    }
+
+    if data.len() * bits as usize % 8 >= bits as usize {
+        return Err(Error::InvalidLength);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,340 @@
+//! [Serde](https://serde.rs) support, so multibase values can be fields of
+//! serialized structures.
+//!
+//! `Base`, `MultibaseString` and `MultibaseStr` implement the serde traits
+//! directly. Byte fields are written as multibase strings with the module
+//! of a base, such as `#[serde(with = "multibase::serde::base58btc")]`.
+//!
+//! Text is only written for human-readable formats such as JSON or YAML.
+//! Binary formats such as bincode or CBOR get the raw bytes of byte fields
+//! and the code of a `Base`, which are smaller and need no encoding.
+//! `MultibaseString` stays text in both, as that is what it holds.
+
+use std::fmt;
+
+#[cfg(feature = "decode")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "decode")]
+use alloc::vec::Vec;
+
+use serde_crate::de::{self, Deserialize, Deserializer, Visitor};
+#[cfg(feature = "decode")]
+use serde_crate::de::SeqAccess;
+#[cfg(all(feature = "encode", feature = "decode"))]
+use serde_crate::ser;
+use serde_crate::ser::{Serialize, Serializer};
+
+use Base;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(all(feature = "encode", feature = "decode"))]
+use normalize;
+#[cfg(feature = "decode")]
+use {Decodable, MultibaseStr, MultibaseString};
+
+/// Serialized as its canonical text, prefix included, as
+/// [`normalize`](../fn.normalize.html) writes it. Without the `encode`
+/// feature the text can't be rewritten, and is serialized as is.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate serde_json;
+/// use multibase::MultibaseString;
+///
+/// let id: MultibaseString = "zCn8eVZg".parse().unwrap();
+/// assert_eq!(serde_json::to_string(&id).unwrap(), "\"zCn8eVZg\"");
+///
+/// let id: MultibaseString = "BMY".parse().unwrap();
+/// assert_eq!(serde_json::to_string(&id).unwrap(), "\"bmy\"");
+/// ```
+#[cfg(feature = "decode")]
+impl Serialize for MultibaseString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "decode")]
+impl Serialize for MultibaseStr {
+    #[cfg(feature = "encode")]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let canonical = normalize(self.as_str()).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&canonical)
+    }
+
+    #[cfg(not(feature = "encode"))]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "decode")]
+struct MultibaseStringVisitor;
+
+#[cfg(feature = "decode")]
+impl<'de> Visitor<'de> for MultibaseStringVisitor {
+    type Value = MultibaseString;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a multibase string")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<MultibaseString, E> {
+        self.visit_string(text.to_string())
+    }
+
+    fn visit_string<E: de::Error>(self, text: String) -> Result<MultibaseString, E> {
+        MultibaseString::new(text).map_err(E::custom)
+    }
+}
+
+/// Deserialized from a string, which is validated like
+/// `MultibaseString::new` does.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate serde_json;
+/// use multibase::MultibaseString;
+///
+/// let id: MultibaseString = serde_json::from_str("\"zCn8eVZg\"").unwrap();
+///
+/// assert_eq!(id.decode(), b"hello");
+/// assert!(serde_json::from_str::<MultibaseString>("\"zCn8eVZ0\"").is_err());
+/// ```
+#[cfg(feature = "decode")]
+impl<'de> Deserialize<'de> for MultibaseString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<MultibaseString, D::Error> {
+        deserializer.deserialize_string(MultibaseStringVisitor)
+    }
+}
+
+#[cfg(feature = "decode")]
+struct MultibaseStrVisitor;
+
+#[cfg(feature = "decode")]
+impl<'de> Visitor<'de> for MultibaseStrVisitor {
+    type Value = &'de MultibaseStr;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a borrowed multibase string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, text: &'de str) -> Result<&'de MultibaseStr, E> {
+        MultibaseStr::new(text).map_err(E::custom)
+    }
+}
+
+/// Borrowed from the input, which only works for formats and inputs that
+/// hold the text verbatim, such as JSON strings without escapes.
+#[cfg(feature = "decode")]
+impl<'de: 'a, 'a> Deserialize<'de> for &'a MultibaseStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<&'a MultibaseStr, D::Error> {
+        deserializer.deserialize_str(MultibaseStrVisitor)
+    }
+}
+
+/// Serialized as its canonical name, such as `base58btc`, or as its code
+/// in binary formats. Use [`base_code`](base_code/index.html) to serialize
+/// the code in human-readable formats too.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate serde_json;
+/// use multibase::Base;
+///
+/// assert_eq!(serde_json::to_string(&Base::Base58btc).unwrap(), "\"base58btc\"");
+/// ```
+impl Serialize for Base {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.name())
+        } else {
+            serializer.serialize_char(self.code())
+        }
+    }
+}
+
+struct BaseVisitor;
+
+impl<'de> Visitor<'de> for BaseVisitor {
+    type Value = Base;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a multibase code or name")
+    }
+
+    fn visit_char<E: de::Error>(self, code: char) -> Result<Base, E> {
+        Base::from_code(code).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Base, E> {
+        let mut chars = text.chars();
+
+        // Codes are a single character and names are longer.
+        match (chars.next(), chars.next()) {
+            (Some(code), None) => self.visit_char(code),
+            _ => text.parse().map_err(E::custom),
+        }
+    }
+}
+
+/// Deserialized from either its code, such as `z`, or its canonical name,
+/// such as `base58btc`. Binary formats only hold the code.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate serde_json;
+/// use multibase::Base;
+///
+/// assert_eq!(serde_json::from_str::<Base>("\"z\"").unwrap(), Base::Base58btc);
+/// assert_eq!(serde_json::from_str::<Base>("\"base58btc\"").unwrap(), Base::Base58btc);
+/// ```
+impl<'de> Deserialize<'de> for Base {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Base, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BaseVisitor)
+        } else {
+            deserializer.deserialize_char(BaseVisitor)
+        }
+    }
+}
+
+/// Serialize a `Base` as its code rather than its name, for use with
+/// `#[serde(with = "multibase::base_code")]`. Deserializing accepts both,
+/// like `Base` itself does.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate multibase;
+/// # extern crate serde_json;
+/// use multibase::{base_code, Base};
+///
+/// let mut json = Vec::new();
+/// base_code::serialize(&Base::Base58btc, &mut serde_json::Serializer::new(&mut json)).unwrap();
+///
+/// assert_eq!(json, b"\"z\"");
+/// ```
+pub mod base_code {
+    use serde_crate::de::Deserializer;
+    use serde_crate::ser::Serializer;
+
+    use serde_crate::{Deserialize, Serialize};
+
+    use Base;
+
+    /// Serialize the code of the base.
+    pub fn serialize<S: Serializer>(base: &Base, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_char(base.code())
+        } else {
+            base.serialize(serializer)
+        }
+    }
+
+    /// Deserialize a base from its code or its name.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Base, D::Error> {
+        Base::deserialize(deserializer)
+    }
+}
+
+/// Serialize `data` as a multibase string in `base`, or as is in binary
+/// formats.
+#[cfg(feature = "encode")]
+fn serialize_bytes<S: Serializer>(base: Base, data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&data.encode(base))
+    } else {
+        serializer.serialize_bytes(data)
+    }
+}
+
+#[cfg(feature = "decode")]
+struct BytesVisitor;
+
+#[cfg(feature = "decode")]
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a multibase string")
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Vec<u8>, E> {
+        text.decode().map(|decoded| decoded.into_bytes()).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, data: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(data.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, data: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(data)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(byte) = seq.next_element()? {
+            data.push(byte);
+        }
+
+        Ok(data)
+    }
+}
+
+/// Deserialize the data of a multibase string in any base, or the bytes
+/// as is in binary formats.
+#[cfg(feature = "decode")]
+fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+macro_rules! build_serde_modules {
+    {$( $(#[$attr:meta])* $val:literal => $var:ident, $module:ident: $name:literal, $alph:literal ($status:ident, $padded:literal), )*} => {
+        $(
+            #[doc = concat!("Write byte fields as ", $name, " multibase strings, for use with")]
+            #[doc = concat!("`#[serde(with = \"multibase::serde::", $name, "\")]`.")]
+            ///
+            /// Any base is accepted when deserializing, since the string
+            /// carries its own prefix. Binary formats get the bytes as is.
+            pub mod $module {
+                #[cfg(feature = "decode")]
+                use serde_crate::de::Deserializer;
+                #[cfg(feature = "encode")]
+                use serde_crate::ser::Serializer;
+
+                #[cfg(feature = "encode")]
+                use Base;
+
+                /// Serialize the bytes as a multibase string.
+                #[cfg(feature = "encode")]
+                pub fn serialize<T, S>(data: &T, serializer: S) -> Result<S::Ok, S::Error>
+                    where T: AsRef<[u8]> + ?Sized, S: Serializer
+                {
+                    super::serialize_bytes(Base::$var, data.as_ref(), serializer)
+                }
+
+                /// Deserialize the bytes from a multibase string.
+                #[cfg(feature = "decode")]
+                pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<::alloc::vec::Vec<u8>, D::Error> {
+                    super::deserialize_bytes(deserializer)
+                }
+            }
+        )*
+    }
+}
+
+for_each_base!(build_serde_modules);
@@ -0,0 +1,1182 @@
+//! Vectorized kernels for the bitwise bases. The best ones the CPU
+//! supports are picked on first use: AVX2 or SSSE3 on x86_64, detected at
+//! runtime, and simd128 on wasm32 when it is enabled at compile time. The
+//! NEON kernels for aarch64 are only built for the tests until they have
+//! been run against the scalar code on aarch64 hardware. Without the `std` feature there is no runtime
+//! detection, so only the target features enabled at compile time are
+//! used. Every kernel handles a prefix of its input in whole
+//! vectors and returns how far it got, leaving the rest to the scalar
+//! code; a vector with a character outside the alphabet is left to it
+//! too, so errors are reported the same way.
+
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "decode")]
+use lookup::Lookup;
+
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+use std::arch::aarch64::*;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use std::arch::wasm32::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+/// The part of a base64 alphabet shared by the standard and URL-safe ones.
+#[cfg(target_arch = "x86_64")]
+const BASE64_LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+#[cfg(target_arch = "x86_64")]
+fn is_base64(alphabet: &[u8]) -> bool {
+    alphabet.len() == 64 && alphabet[..62] == *BASE64_LETTERS
+}
+
+/// Runs of consecutive characters in an alphabet, as `(first character,
+/// value of it, length)`. Unused entries have a length of zero.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+type Runs = [(u8, u8, u8); 4];
+
+/// Split an `alphabet` into runs of consecutive characters, if there are
+/// few enough for the SSSE3 decoders.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+fn runs(alphabet: &[u8]) -> Option<Runs> {
+    let mut runs = [(0u8, 0u8, 0u8); 4];
+    let mut count = 0;
+
+    for (i, &c) in alphabet.iter().enumerate() {
+        if count > 0 {
+            let run = &mut runs[count - 1];
+
+            if c == run.0.wrapping_add(run.2) {
+                run.2 += 1;
+                continue;
+            }
+        }
+
+        *runs.get_mut(count)? = (c, i as u8, 1);
+        count += 1;
+    }
+
+    Some(runs)
+}
+
+/// Encodes a prefix of `data` with an `alphabet` into `out`, returning how
+/// far it got in both. Unsafe, as it may use instructions the CPU doesn't
+/// have.
+#[cfg(feature = "encode")]
+type EncodeKernel = unsafe fn(&[u8], &[u8], &mut [u8]) -> (usize, usize);
+
+/// Decodes a prefix of `data` like an `EncodeKernel` encodes, given the
+/// lookup of the alphabet as well.
+#[cfg(feature = "decode")]
+type DecodeKernel = unsafe fn(&[u8], Lookup, &[u8], &mut [u8]) -> (usize, usize);
+
+/// A kernel for every base with vectorized ones.
+struct Kernels {
+    #[cfg(feature = "encode")]
+    encode_base16: EncodeKernel,
+    #[cfg(feature = "decode")]
+    decode_base16: DecodeKernel,
+    #[cfg(feature = "encode")]
+    encode_base32: EncodeKernel,
+    #[cfg(feature = "decode")]
+    decode_base32: DecodeKernel,
+    #[cfg(feature = "encode")]
+    encode_base64: EncodeKernel,
+    #[cfg(feature = "decode")]
+    decode_base64: DecodeKernel,
+}
+
+/// Leave all of the input to the scalar code.
+#[cfg(feature = "encode")]
+unsafe fn scalar_encode(_: &[u8], _: &[u8], _: &mut [u8]) -> (usize, usize) {
+    (0, 0)
+}
+
+/// Leave all of the input to the scalar code.
+#[cfg(feature = "decode")]
+unsafe fn scalar_decode(_: &[u8], _: Lookup, _: &[u8], _: &mut [u8]) -> (usize, usize) {
+    (0, 0)
+}
+
+static SCALAR: Kernels = Kernels {
+    #[cfg(feature = "encode")]
+    encode_base16: scalar_encode,
+    #[cfg(feature = "decode")]
+    decode_base16: scalar_decode,
+    #[cfg(feature = "encode")]
+    encode_base32: scalar_encode,
+    #[cfg(feature = "decode")]
+    decode_base32: scalar_decode,
+    #[cfg(feature = "encode")]
+    encode_base64: scalar_encode,
+    #[cfg(feature = "decode")]
+    decode_base64: scalar_decode,
+};
+
+#[cfg(target_arch = "x86_64")]
+static SSSE3: Kernels = Kernels {
+    #[cfg(feature = "encode")]
+    encode_base16: encode_base16_ssse3,
+    #[cfg(feature = "decode")]
+    decode_base16: decode_base16_ssse3,
+    #[cfg(feature = "encode")]
+    encode_base32: encode_base32_ssse3,
+    #[cfg(feature = "decode")]
+    decode_base32: decode_base32_ssse3,
+    #[cfg(feature = "encode")]
+    encode_base64: encode_base64_ssse3,
+    #[cfg(feature = "decode")]
+    decode_base64: decode_base64_ssse3,
+};
+
+#[cfg(target_arch = "x86_64")]
+static AVX2: Kernels = Kernels {
+    #[cfg(feature = "encode")]
+    encode_base64: encode_base64_avx2,
+    #[cfg(feature = "decode")]
+    decode_base64: decode_base64_avx2,
+    ..SSSE3
+};
+
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+static NEON: Kernels = Kernels {
+    #[cfg(feature = "encode")]
+    encode_base16: encode_base16_neon,
+    #[cfg(feature = "decode")]
+    decode_base16: decode_base16_neon,
+    #[cfg(feature = "encode")]
+    encode_base32: encode_base32_neon,
+    #[cfg(feature = "decode")]
+    decode_base32: decode_base32_neon,
+    #[cfg(feature = "encode")]
+    encode_base64: encode_base64_neon,
+    #[cfg(feature = "decode")]
+    decode_base64: decode_base64_neon,
+};
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+static SIMD128: Kernels = Kernels {
+    #[cfg(feature = "encode")]
+    encode_base16: encode_base16_simd128,
+    #[cfg(feature = "decode")]
+    decode_base16: decode_base16_simd128,
+    #[cfg(feature = "encode")]
+    encode_base32: encode_base32_simd128,
+    #[cfg(feature = "decode")]
+    decode_base32: decode_base32_simd128,
+    #[cfg(feature = "encode")]
+    encode_base64: encode_base64_simd128,
+    #[cfg(feature = "decode")]
+    decode_base64: decode_base64_simd128,
+};
+
+/// Whether the CPU supports a target feature: detected at runtime with
+/// `std`, and as enabled at compile time without.
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+macro_rules! detected {
+    ($feature:tt) => { is_x86_feature_detected!($feature) };
+}
+
+#[cfg(all(not(feature = "std"), target_arch = "x86_64"))]
+macro_rules! detected {
+    ($feature:tt) => { cfg!(target_feature = $feature) };
+}
+
+/// Pick the best kernels the CPU supports.
+#[allow(unreachable_code)]
+fn detect() -> &'static Kernels {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if detected!("avx2") {
+            return &AVX2;
+        }
+
+        if detected!("ssse3") {
+            return &SSSE3;
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    return &SIMD128;
+
+    &SCALAR
+}
+
+#[cfg(feature = "std")]
+fn kernels() -> &'static Kernels {
+    static KERNELS: OnceLock<&'static Kernels> = OnceLock::new();
+
+    #[cfg(all(test, feature = "encode", feature = "decode"))]
+    {
+        if let Some(kernels) = tests::OVERRIDE.with(|kernels| kernels.get()) {
+            return kernels;
+        }
+    }
+
+    KERNELS.get_or_init(detect)
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+fn kernels() -> &'static Kernels {
+    detect()
+}
+
+/// Encode a prefix of `data` with a base16 `alphabet`, returning the number
+/// of bytes read and characters written.
+#[cfg(feature = "encode")]
+pub fn encode_base16(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    // The kernels were picked for this CPU.
+    unsafe { (kernels().encode_base16)(alphabet, data, out) }
+}
+
+/// Decode a prefix of `data` with a base16 `alphabet`, returning the number
+/// of characters read and bytes written.
+#[cfg(feature = "decode")]
+pub fn decode_base16(alphabet: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    unsafe { (kernels().decode_base16)(alphabet, lookup, data, out) }
+}
+
+/// Encode a prefix of `data` with a base32 `alphabet`, returning the number
+/// of bytes read and characters written.
+#[cfg(feature = "encode")]
+pub fn encode_base32(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    unsafe { (kernels().encode_base32)(alphabet, data, out) }
+}
+
+/// Decode a prefix of `data` with a base32 `alphabet`, returning the number
+/// of characters read and bytes written.
+#[cfg(feature = "decode")]
+pub fn decode_base32(alphabet: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    unsafe { (kernels().decode_base32)(alphabet, lookup, data, out) }
+}
+
+/// Encode a prefix of `data` with a base64 `alphabet`, returning the number
+/// of bytes read and characters written.
+#[cfg(feature = "encode")]
+pub fn encode_base64(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    unsafe { (kernels().encode_base64)(alphabet, data, out) }
+}
+
+/// Decode a prefix of `data` with a base64 `alphabet`, returning the number
+/// of characters read and bytes written.
+#[cfg(feature = "decode")]
+pub fn decode_base64(alphabet: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    unsafe { (kernels().decode_base64)(alphabet, lookup, data, out) }
+}
+
+/// 16 bytes to 32 characters at a time: each nibble is mapped onto the
+/// alphabet with a lookup, and the high and low ones are interleaved.
+#[cfg(all(target_arch = "x86_64", feature = "encode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_base16_ssse3(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = _mm_loadu_si128(alphabet.as_ptr() as *const __m128i);
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 16 && out.len() - written >= 32 {
+        let input = _mm_loadu_si128(data.as_ptr().add(read) as *const __m128i);
+
+        let high = _mm_shuffle_epi8(table, _mm_and_si128(_mm_srli_epi16(input, 4), _mm_set1_epi8(0x0f)));
+        let low = _mm_shuffle_epi8(table, _mm_and_si128(input, _mm_set1_epi8(0x0f)));
+
+        let out = out.as_mut_ptr().add(written) as *mut __m128i;
+        _mm_storeu_si128(out, _mm_unpacklo_epi8(high, low));
+        _mm_storeu_si128(out.add(1), _mm_unpackhi_epi8(high, low));
+
+        read += 16;
+        written += 32;
+    }
+
+    (read, written)
+}
+
+/// 32 characters to 16 bytes at a time: the characters are mapped onto
+/// their values run by run, and pairs of them are packed with a
+/// multiply-add.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_base16_ssse3(alphabet: &[u8], _: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let runs = match runs(alphabet) {
+        Some(runs) => runs,
+        None => return (0, 0),
+    };
+
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 32 && out.len() - written >= 16 {
+        let input = data.as_ptr().add(read) as *const __m128i;
+
+        let (first_valid, first) = run_values(&runs, _mm_loadu_si128(input));
+        let (second_valid, second) = run_values(&runs, _mm_loadu_si128(input.add(1)));
+
+        if _mm_movemask_epi8(_mm_and_si128(first_valid, second_valid)) != 0xffff {
+            break;
+        }
+
+        let first = _mm_maddubs_epi16(first, _mm_set1_epi16(0x0110));
+        let second = _mm_maddubs_epi16(second, _mm_set1_epi16(0x0110));
+
+        _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, _mm_packus_epi16(first, second));
+
+        read += 32;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 12 bytes to 16 characters at a time: the bytes are spread over 32-bit
+/// lanes, split into 6-bit indices with multiplies standing in for
+/// per-lane shifts, and mapped onto the alphabet by adding an offset looked
+/// up per index range.
+#[cfg(all(target_arch = "x86_64", feature = "encode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_base64_ssse3(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    if !is_base64(alphabet) {
+        return (0, 0);
+    }
+
+    let spread = _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10);
+    let offsets = base64_offsets(alphabet);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Loads take 16 bytes, of which 12 are used.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let input = _mm_loadu_si128(data.as_ptr().add(read) as *const __m128i);
+        let input = _mm_shuffle_epi8(input, spread);
+
+        let high = _mm_mulhi_epu16(_mm_and_si128(input, _mm_set1_epi32(0x0fc0_fc00)), _mm_set1_epi32(0x0400_0040));
+        let low = _mm_mullo_epi16(_mm_and_si128(input, _mm_set1_epi32(0x003f_03f0)), _mm_set1_epi32(0x0100_0010));
+        let indices = _mm_or_si128(high, low);
+
+        let range = _mm_subs_epu8(indices, _mm_set1_epi8(51));
+        let letters = _mm_and_si128(_mm_cmpgt_epi8(_mm_set1_epi8(26), indices), _mm_set1_epi8(13));
+        let chars = _mm_add_epi8(_mm_shuffle_epi8(offsets, _mm_or_si128(range, letters)), indices);
+
+        _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, chars);
+
+        read += 12;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// Offsets from base64 index to character, looked up by range: 26..52,
+/// then 52..62 one by one, 62, 63, and 0..26.
+#[cfg(all(target_arch = "x86_64", feature = "encode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn base64_offsets(alphabet: &[u8]) -> __m128i {
+    _mm_setr_epi8(
+        71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4,
+        (alphabet[62] as i8).wrapping_sub(62), (alphabet[63] as i8).wrapping_sub(63), 65, 0, 0,
+    )
+}
+
+/// Mask of the lanes of `c` between `low` and `high`, inclusive.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn in_range(c: __m128i, low: u8, high: u8) -> __m128i {
+    _mm_and_si128(
+        _mm_cmpgt_epi8(c, _mm_set1_epi8(low as i8 - 1)),
+        _mm_cmpgt_epi8(_mm_set1_epi8(high as i8 + 1), c),
+    )
+}
+
+/// 16 characters to 12 bytes at a time: the characters are mapped onto
+/// their values by range, then packed pairwise with multiply-adds and
+/// shuffled into place.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_base64_ssse3(alphabet: &[u8], _: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    if !is_base64(alphabet) {
+        return (0, 0);
+    }
+
+    let (c62, c63) = (alphabet[62], alphabet[63]);
+    let pack = _mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1);
+    let (mut read, mut written) = (0, 0);
+
+    // Stores write 16 bytes, of which 12 are kept.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let c = _mm_loadu_si128(data.as_ptr().add(read) as *const __m128i);
+
+        let upper = in_range(c, b'A', b'Z');
+        let lower = in_range(c, b'a', b'z');
+        let digit = in_range(c, b'0', b'9');
+        let is_62 = _mm_cmpeq_epi8(c, _mm_set1_epi8(c62 as i8));
+        let is_63 = _mm_cmpeq_epi8(c, _mm_set1_epi8(c63 as i8));
+
+        let valid = _mm_or_si128(_mm_or_si128(upper, lower), _mm_or_si128(digit, _mm_or_si128(is_62, is_63)));
+
+        if _mm_movemask_epi8(valid) != 0xffff {
+            break;
+        }
+
+        let values = _mm_or_si128(
+            _mm_or_si128(
+                _mm_and_si128(upper, _mm_sub_epi8(c, _mm_set1_epi8(65))),
+                _mm_and_si128(lower, _mm_sub_epi8(c, _mm_set1_epi8(71))),
+            ),
+            _mm_or_si128(
+                _mm_and_si128(digit, _mm_add_epi8(c, _mm_set1_epi8(4))),
+                _mm_or_si128(
+                    _mm_and_si128(is_62, _mm_set1_epi8(62)),
+                    _mm_and_si128(is_63, _mm_set1_epi8(63)),
+                ),
+            ),
+        );
+
+        let pairs = _mm_maddubs_epi16(values, _mm_set1_epi32(0x0140_0140));
+        let triples = _mm_madd_epi16(pairs, _mm_set1_epi32(0x0001_1000));
+
+        _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, _mm_shuffle_epi8(triples, pack));
+
+        read += 16;
+        written += 12;
+    }
+
+    (read, written)
+}
+
+/// The SSSE3 encoder on two 128-bit lanes at once, 24 bytes to 32
+/// characters at a time.
+#[cfg(all(target_arch = "x86_64", feature = "encode"))]
+#[target_feature(enable = "avx2")]
+unsafe fn encode_base64_avx2(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    if !is_base64(alphabet) {
+        return (0, 0);
+    }
+
+    let spread = _mm256_broadcastsi128_si256(_mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10));
+    let offsets = _mm256_broadcastsi128_si256(base64_offsets(alphabet));
+
+    let (mut read, mut written) = (0, 0);
+
+    // Each lane loads 16 bytes, of which 12 are used.
+    while data.len() - read >= 28 && out.len() - written >= 32 {
+        let input = data.as_ptr().add(read);
+        let input = _mm256_inserti128_si256::<1>(
+            _mm256_castsi128_si256(_mm_loadu_si128(input as *const __m128i)),
+            _mm_loadu_si128(input.add(12) as *const __m128i),
+        );
+        let input = _mm256_shuffle_epi8(input, spread);
+
+        let high = _mm256_mulhi_epu16(
+            _mm256_and_si256(input, _mm256_set1_epi32(0x0fc0_fc00)),
+            _mm256_set1_epi32(0x0400_0040),
+        );
+        let low = _mm256_mullo_epi16(
+            _mm256_and_si256(input, _mm256_set1_epi32(0x003f_03f0)),
+            _mm256_set1_epi32(0x0100_0010),
+        );
+        let indices = _mm256_or_si256(high, low);
+
+        let range = _mm256_subs_epu8(indices, _mm256_set1_epi8(51));
+        let letters = _mm256_and_si256(_mm256_cmpgt_epi8(_mm256_set1_epi8(26), indices), _mm256_set1_epi8(13));
+        let chars = _mm256_add_epi8(_mm256_shuffle_epi8(offsets, _mm256_or_si256(range, letters)), indices);
+
+        _mm256_storeu_si256(out.as_mut_ptr().add(written) as *mut __m256i, chars);
+
+        read += 24;
+        written += 32;
+    }
+
+    let (rest, rest_written) = encode_base64_ssse3(alphabet, &data[read..], &mut out[written..]);
+
+    (read + rest, written + rest_written)
+}
+
+/// Mask of the lanes of `c` between `low` and `high`, inclusive.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "avx2")]
+unsafe fn in_range_avx2(c: __m256i, low: u8, high: u8) -> __m256i {
+    _mm256_and_si256(
+        _mm256_cmpgt_epi8(c, _mm256_set1_epi8(low as i8 - 1)),
+        _mm256_cmpgt_epi8(_mm256_set1_epi8(high as i8 + 1), c),
+    )
+}
+
+/// The SSSE3 decoder on two 128-bit lanes at once, 32 characters to 24
+/// bytes at a time.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "avx2")]
+unsafe fn decode_base64_avx2(alphabet: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    if !is_base64(alphabet) {
+        return (0, 0);
+    }
+
+    let (c62, c63) = (alphabet[62], alphabet[63]);
+    let pack = _mm256_broadcastsi128_si256(_mm_setr_epi8(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1));
+    let (mut read, mut written) = (0, 0);
+
+    // Each lane stores 16 bytes, of which 12 are kept.
+    while data.len() - read >= 32 && out.len() - written >= 28 {
+        let c = _mm256_loadu_si256(data.as_ptr().add(read) as *const __m256i);
+
+        let upper = in_range_avx2(c, b'A', b'Z');
+        let lower = in_range_avx2(c, b'a', b'z');
+        let digit = in_range_avx2(c, b'0', b'9');
+        let is_62 = _mm256_cmpeq_epi8(c, _mm256_set1_epi8(c62 as i8));
+        let is_63 = _mm256_cmpeq_epi8(c, _mm256_set1_epi8(c63 as i8));
+
+        let valid = _mm256_or_si256(
+            _mm256_or_si256(upper, lower),
+            _mm256_or_si256(digit, _mm256_or_si256(is_62, is_63)),
+        );
+
+        if _mm256_movemask_epi8(valid) != -1 {
+            break;
+        }
+
+        let values = _mm256_or_si256(
+            _mm256_or_si256(
+                _mm256_and_si256(upper, _mm256_sub_epi8(c, _mm256_set1_epi8(65))),
+                _mm256_and_si256(lower, _mm256_sub_epi8(c, _mm256_set1_epi8(71))),
+            ),
+            _mm256_or_si256(
+                _mm256_and_si256(digit, _mm256_add_epi8(c, _mm256_set1_epi8(4))),
+                _mm256_or_si256(
+                    _mm256_and_si256(is_62, _mm256_set1_epi8(62)),
+                    _mm256_and_si256(is_63, _mm256_set1_epi8(63)),
+                ),
+            ),
+        );
+
+        let pairs = _mm256_maddubs_epi16(values, _mm256_set1_epi32(0x0140_0140));
+        let triples = _mm256_madd_epi16(pairs, _mm256_set1_epi32(0x0001_1000));
+        let bytes = _mm256_shuffle_epi8(triples, pack);
+
+        let out = out.as_mut_ptr().add(written);
+        _mm_storeu_si128(out as *mut __m128i, _mm256_castsi256_si128(bytes));
+        _mm_storeu_si128(out.add(12) as *mut __m128i, _mm256_extracti128_si256::<1>(bytes));
+
+        read += 32;
+        written += 24;
+    }
+
+    let (rest, rest_written) = decode_base64_ssse3(alphabet, lookup, &data[read..], &mut out[written..]);
+
+    (read + rest, written + rest_written)
+}
+
+/// 10 bytes to 16 characters at a time: each character's bits are moved
+/// into a 16-bit lane along with the byte after them, shifted down with a
+/// multiply, and mapped onto the alphabet with a lookup in either half.
+#[cfg(all(target_arch = "x86_64", feature = "encode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_base32_ssse3(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let first = _mm_setr_epi8(1, 0, 1, 0, 2, 1, 2, 1, 3, 2, 4, 3, 4, 3, -1, 4);
+    let second = _mm_setr_epi8(6, 5, 6, 5, 7, 6, 7, 6, 8, 7, 9, 8, 9, 8, -1, 9);
+    let shifts = _mm_setr_epi16(1 << 5, 1 << 10, 1 << 7, 1 << 12, 1 << 9, 1 << 6, 1 << 11, 1 << 8);
+
+    let low = _mm_loadu_si128(alphabet.as_ptr() as *const __m128i);
+    let high = _mm_loadu_si128(alphabet.as_ptr().add(16) as *const __m128i);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Loads take 16 bytes, of which 10 are used.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let input = _mm_loadu_si128(data.as_ptr().add(read) as *const __m128i);
+
+        let first = _mm_and_si128(_mm_mulhi_epu16(_mm_shuffle_epi8(input, first), shifts), _mm_set1_epi16(0x1f));
+        let second = _mm_and_si128(_mm_mulhi_epu16(_mm_shuffle_epi8(input, second), shifts), _mm_set1_epi16(0x1f));
+        let indices = _mm_packus_epi16(first, second);
+
+        let upper = _mm_cmpgt_epi8(indices, _mm_set1_epi8(15));
+        let chars = _mm_or_si128(
+            _mm_andnot_si128(upper, _mm_shuffle_epi8(low, indices)),
+            _mm_and_si128(upper, _mm_shuffle_epi8(high, indices)),
+        );
+
+        _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, chars);
+
+        read += 10;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// Map the lanes of `c` onto their values with the runs of an alphabet,
+/// along with a mask of the lanes that are in it.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn run_values(runs: &Runs, c: __m128i) -> (__m128i, __m128i) {
+    let mut valid = _mm_setzero_si128();
+    let mut values = _mm_setzero_si128();
+
+    for &(first, value, len) in runs.iter().take_while(|run| run.2 > 0) {
+        let run = in_range(c, first, first + len - 1);
+        let offset = _mm_set1_epi8(value.wrapping_sub(first) as i8);
+
+        valid = _mm_or_si128(valid, run);
+        values = _mm_or_si128(values, _mm_and_si128(run, _mm_add_epi8(c, offset)));
+    }
+
+    (valid, values)
+}
+
+/// 16 characters to 10 bytes at a time: the characters are mapped onto
+/// their values run by run, then packed with multiply-adds and shifts
+/// into two 40-bit lanes and shuffled into place.
+#[cfg(all(target_arch = "x86_64", feature = "decode"))]
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_base32_ssse3(alphabet: &[u8], _: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let runs = match runs(alphabet) {
+        Some(runs) => runs,
+        None => return (0, 0),
+    };
+
+    let pack = _mm_setr_epi8(4, 3, 2, 1, 0, 12, 11, 10, 9, 8, -1, -1, -1, -1, -1, -1);
+    let (mut read, mut written) = (0, 0);
+
+    // Stores write 16 bytes, of which 10 are kept.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let (valid, values) = run_values(&runs, _mm_loadu_si128(data.as_ptr().add(read) as *const __m128i));
+
+        if _mm_movemask_epi8(valid) != 0xffff {
+            break;
+        }
+
+        let pairs = _mm_maddubs_epi16(values, _mm_set1_epi16(0x0120));
+        let quads = _mm_madd_epi16(pairs, _mm_set1_epi32(0x0001_0400));
+        let octets = _mm_or_si128(
+            _mm_slli_epi64(_mm_and_si128(quads, _mm_set1_epi64x(0xffff_ffff)), 20),
+            _mm_srli_epi64(quads, 32),
+        );
+
+        _mm_storeu_si128(out.as_mut_ptr().add(written) as *mut __m128i, _mm_shuffle_epi8(octets, pack));
+
+        read += 16;
+        written += 10;
+    }
+
+    (read, written)
+}
+
+/// The ASCII `lookup` table, as two halves for four-register lookups.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn neon_lookup(lookup: &[u8; 128]) -> (uint8x16x4_t, uint8x16x4_t) {
+    (vld1q_u8_x4(lookup.as_ptr()), vld1q_u8_x4(lookup.as_ptr().add(64)))
+}
+
+/// Map the lanes of `c` onto their values, `0xff` for characters not in
+/// the alphabet.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn neon_values(lookup: &(uint8x16x4_t, uint8x16x4_t), c: uint8x16_t) -> uint8x16_t {
+    // Lookups out of range of a table give zero, or keep the value from
+    // the first one, so non-ASCII characters are marked separately.
+    let values = vqtbl4q_u8(lookup.0, c);
+    let values = vqtbx4q_u8(values, lookup.1, vsubq_u8(c, vdupq_n_u8(64)));
+
+    vorrq_u8(values, vcgeq_u8(c, vdupq_n_u8(0x80)))
+}
+
+/// 16 bytes to 32 characters at a time: each nibble is mapped onto the
+/// alphabet with a lookup, and the high and low ones are stored
+/// interleaved.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn encode_base16_neon(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = vld1q_u8(alphabet.as_ptr());
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 16 && out.len() - written >= 32 {
+        let input = vld1q_u8(data.as_ptr().add(read));
+
+        let high = vqtbl1q_u8(table, vshrq_n_u8::<4>(input));
+        let low = vqtbl1q_u8(table, vandq_u8(input, vdupq_n_u8(0x0f)));
+
+        vst2q_u8(out.as_mut_ptr().add(written), uint8x16x2_t(high, low));
+
+        read += 16;
+        written += 32;
+    }
+
+    (read, written)
+}
+
+/// 32 characters to 16 bytes at a time: the characters are loaded
+/// deinterleaved into high and low nibbles and looked up.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn decode_base16_neon(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| neon_lookup(ascii));
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 32 && out.len() - written >= 16 {
+        let input = vld2q_u8(data.as_ptr().add(read));
+
+        let high = neon_values(&lookup, input.0);
+        let low = neon_values(&lookup, input.1);
+
+        if vmaxvq_u8(vorrq_u8(high, low)) >= 16 {
+            break;
+        }
+
+        vst1q_u8(out.as_mut_ptr().add(written), vorrq_u8(vshlq_n_u8::<4>(high), low));
+
+        read += 32;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 10 bytes to 16 characters at a time: each character's bits are moved
+/// into a 16-bit lane along with the byte after them, shifted down by a
+/// per-lane amount, narrowed and looked up.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn encode_base32_neon(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = vld1q_u8_x2(alphabet.as_ptr());
+    let first = vld1q_u8([1, 0, 1, 0, 2, 1, 2, 1, 3, 2, 4, 3, 4, 3, 0xff, 4].as_ptr());
+    let second = vld1q_u8([6, 5, 6, 5, 7, 6, 7, 6, 8, 7, 9, 8, 9, 8, 0xff, 9].as_ptr());
+    let shifts = vld1q_s16([-11, -6, -9, -4, -7, -10, -5, -8].as_ptr());
+    let mask = vdupq_n_u16(0x1f);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Loads take 16 bytes, of which 10 are used.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let input = vld1q_u8(data.as_ptr().add(read));
+
+        let first = vandq_u16(vshlq_u16(vreinterpretq_u16_u8(vqtbl1q_u8(input, first)), shifts), mask);
+        let second = vandq_u16(vshlq_u16(vreinterpretq_u16_u8(vqtbl1q_u8(input, second)), shifts), mask);
+        let indices = vcombine_u8(vmovn_u16(first), vmovn_u16(second));
+
+        vst1q_u8(out.as_mut_ptr().add(written), vqtbl2q_u8(table, indices));
+
+        read += 10;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 16 characters to 10 bytes at a time: the values are merged pairwise
+/// into ever wider lanes, ending up as two 40-bit ones that are shuffled
+/// into place.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn decode_base32_neon(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| neon_lookup(ascii));
+    let pack = vld1q_u8([4, 3, 2, 1, 0, 12, 11, 10, 9, 8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff].as_ptr());
+    let (mut read, mut written) = (0, 0);
+
+    // Stores write 16 bytes, of which 10 are kept.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let values = neon_values(&lookup, vld1q_u8(data.as_ptr().add(read)));
+
+        if vmaxvq_u8(values) >= 32 {
+            break;
+        }
+
+        let pairs = vreinterpretq_u16_u8(values);
+        let pairs = vorrq_u16(vshlq_n_u16::<5>(vandq_u16(pairs, vdupq_n_u16(0xff))), vshrq_n_u16::<8>(pairs));
+        let quads = vreinterpretq_u32_u16(pairs);
+        let quads = vorrq_u32(vshlq_n_u32::<10>(vandq_u32(quads, vdupq_n_u32(0xffff))), vshrq_n_u32::<16>(quads));
+        let octets = vreinterpretq_u64_u32(quads);
+        let octets = vorrq_u64(
+            vshlq_n_u64::<20>(vandq_u64(octets, vdupq_n_u64(0xffff_ffff))),
+            vshrq_n_u64::<32>(octets),
+        );
+
+        vst1q_u8(out.as_mut_ptr().add(written), vqtbl1q_u8(vreinterpretq_u8_u64(octets), pack));
+
+        read += 16;
+        written += 10;
+    }
+
+    (read, written)
+}
+
+/// 48 bytes to 64 characters at a time: the bytes are loaded
+/// deinterleaved in threes, split into 6-bit indices with shifts, and
+/// looked up in the whole alphabet at once.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn encode_base64_neon(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = vld1q_u8_x4(alphabet.as_ptr());
+    let mask = vdupq_n_u8(0x3f);
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 48 && out.len() - written >= 64 {
+        let uint8x16x3_t(a, b, c) = vld3q_u8(data.as_ptr().add(read));
+
+        let indices = uint8x16x4_t(
+            vshrq_n_u8::<2>(a),
+            vandq_u8(vorrq_u8(vshlq_n_u8::<4>(a), vshrq_n_u8::<4>(b)), mask),
+            vandq_u8(vorrq_u8(vshlq_n_u8::<2>(b), vshrq_n_u8::<6>(c)), mask),
+            vandq_u8(c, mask),
+        );
+
+        vst4q_u8(out.as_mut_ptr().add(written), uint8x16x4_t(
+            vqtbl4q_u8(table, indices.0),
+            vqtbl4q_u8(table, indices.1),
+            vqtbl4q_u8(table, indices.2),
+            vqtbl4q_u8(table, indices.3),
+        ));
+
+        read += 48;
+        written += 64;
+    }
+
+    (read, written)
+}
+
+/// 64 characters to 48 bytes at a time: the characters are loaded
+/// deinterleaved in fours, looked up, and merged back into threes with
+/// shifts.
+#[cfg(all(target_arch = "aarch64", test, feature = "std", feature = "encode", feature = "decode"))]
+#[target_feature(enable = "neon")]
+unsafe fn decode_base64_neon(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| neon_lookup(ascii));
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 64 && out.len() - written >= 48 {
+        let input = vld4q_u8(data.as_ptr().add(read));
+
+        let a = neon_values(&lookup, input.0);
+        let b = neon_values(&lookup, input.1);
+        let c = neon_values(&lookup, input.2);
+        let d = neon_values(&lookup, input.3);
+
+        if vmaxvq_u8(vorrq_u8(vorrq_u8(a, b), vorrq_u8(c, d))) >= 64 {
+            break;
+        }
+
+        vst3q_u8(out.as_mut_ptr().add(written), uint8x16x3_t(
+            vorrq_u8(vshlq_n_u8::<2>(a), vshrq_n_u8::<4>(b)),
+            vorrq_u8(vshlq_n_u8::<4>(b), vshrq_n_u8::<2>(c)),
+            vorrq_u8(vshlq_n_u8::<6>(c), d),
+        ));
+
+        read += 64;
+        written += 48;
+    }
+
+    (read, written)
+}
+
+/// Load a table of `N` vectors.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+unsafe fn wasm_table<const N: usize>(table: &[u8]) -> [v128; N] {
+    std::array::from_fn(|i| v128_load(table[16 * i..].as_ptr() as *const v128))
+}
+
+/// Look up the lanes of `indices` in a table of vectors, giving zero for
+/// indices past its end.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[target_feature(enable = "simd128")]
+unsafe fn wasm_lookup<const N: usize>(table: &[v128; N], indices: v128) -> v128 {
+    let mut found = u8x16_splat(0);
+
+    // Swizzles give zero for indices out of their 16 lanes, so each part
+    // of the table only contributes its own range.
+    for (i, &part) in table.iter().enumerate() {
+        found = v128_or(found, i8x16_swizzle(part, u8x16_sub(indices, u8x16_splat(16 * i as u8))));
+    }
+
+    found
+}
+
+/// The ASCII `lookup` table.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "decode"))]
+unsafe fn wasm_decode_table(lookup: &[u8; 128]) -> [v128; 8] {
+    wasm_table(lookup)
+}
+
+/// Map the lanes of `c` onto their values, `0xff` for characters not in
+/// the alphabet.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "decode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn wasm_values(lookup: &[v128; 8], c: v128) -> v128 {
+    v128_or(wasm_lookup(lookup, c), u8x16_ge(c, u8x16_splat(0x80)))
+}
+
+/// The high 16 bits of the products of the lanes of `a` and `b`.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "encode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn wasm_mulhi(a: v128, b: v128) -> v128 {
+    u16x8_narrow_i32x4(
+        u32x4_shr(u32x4_extmul_low_u16x8(a, b), 16),
+        u32x4_shr(u32x4_extmul_high_u16x8(a, b), 16),
+    )
+}
+
+/// 16 bytes to 32 characters at a time: each nibble is looked up, and the
+/// high and low ones are interleaved.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "encode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn encode_base16_simd128(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = wasm_table::<1>(alphabet);
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 16 && out.len() - written >= 32 {
+        let input = v128_load(data.as_ptr().add(read) as *const v128);
+
+        let high = wasm_lookup(&table, u8x16_shr(input, 4));
+        let low = wasm_lookup(&table, v128_and(input, u8x16_splat(0x0f)));
+
+        let out = out.as_mut_ptr().add(written) as *mut v128;
+        v128_store(out, i8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(high, low));
+        v128_store(out.add(1), i8x16_shuffle::<8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31>(high, low));
+
+        read += 16;
+        written += 32;
+    }
+
+    (read, written)
+}
+
+/// 32 characters to 16 bytes at a time: the characters are looked up, and
+/// pairs of them are merged in 16-bit lanes and narrowed.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "decode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn decode_base16_simd128(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| wasm_decode_table(ascii));
+    let (mut read, mut written) = (0, 0);
+
+    while data.len() - read >= 32 && out.len() - written >= 16 {
+        let input = data.as_ptr().add(read) as *const v128;
+
+        let first = wasm_values(&lookup, v128_load(input));
+        let second = wasm_values(&lookup, v128_load(input.add(1)));
+
+        if !u8x16_all_true(u8x16_lt(v128_or(first, second), u8x16_splat(16))) {
+            break;
+        }
+
+        let first = v128_or(i16x8_shl(v128_and(first, u16x8_splat(0xff)), 4), u16x8_shr(first, 8));
+        let second = v128_or(i16x8_shl(v128_and(second, u16x8_splat(0xff)), 4), u16x8_shr(second, 8));
+
+        v128_store(out.as_mut_ptr().add(written) as *mut v128, u8x16_narrow_i16x8(first, second));
+
+        read += 32;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 10 bytes to 16 characters at a time: each character's bits are moved
+/// into a 16-bit lane along with the byte after them, shifted down with a
+/// multiply, narrowed and looked up.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "encode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn encode_base32_simd128(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = wasm_table::<2>(alphabet);
+    let first = i8x16(1, 0, 1, 0, 2, 1, 2, 1, 3, 2, 4, 3, 4, 3, -1, 4);
+    let second = i8x16(6, 5, 6, 5, 7, 6, 7, 6, 8, 7, 9, 8, 9, 8, -1, 9);
+    let shifts = u16x8(1 << 5, 1 << 10, 1 << 7, 1 << 12, 1 << 9, 1 << 6, 1 << 11, 1 << 8);
+    let mask = u16x8_splat(0x1f);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Loads take 16 bytes, of which 10 are used.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let input = v128_load(data.as_ptr().add(read) as *const v128);
+
+        let first = v128_and(wasm_mulhi(i8x16_swizzle(input, first), shifts), mask);
+        let second = v128_and(wasm_mulhi(i8x16_swizzle(input, second), shifts), mask);
+        let indices = u8x16_narrow_i16x8(first, second);
+
+        v128_store(out.as_mut_ptr().add(written) as *mut v128, wasm_lookup(&table, indices));
+
+        read += 10;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 16 characters to 10 bytes at a time: the values are merged pairwise
+/// into ever wider lanes, ending up as two 40-bit ones that are shuffled
+/// into place.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "decode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn decode_base32_simd128(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| wasm_decode_table(ascii));
+    let pack = i8x16(4, 3, 2, 1, 0, 12, 11, 10, 9, 8, -1, -1, -1, -1, -1, -1);
+    let (mut read, mut written) = (0, 0);
+
+    // Stores write 16 bytes, of which 10 are kept.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let values = wasm_values(&lookup, v128_load(data.as_ptr().add(read) as *const v128));
+
+        if !u8x16_all_true(u8x16_lt(values, u8x16_splat(32))) {
+            break;
+        }
+
+        let pairs = v128_or(i16x8_shl(v128_and(values, u16x8_splat(0xff)), 5), u16x8_shr(values, 8));
+        let quads = v128_or(i32x4_shl(v128_and(pairs, u32x4_splat(0xffff)), 10), u32x4_shr(pairs, 16));
+        let octets = v128_or(i64x2_shl(v128_and(quads, u64x2_splat(0xffff_ffff)), 20), u64x2_shr(quads, 32));
+
+        v128_store(out.as_mut_ptr().add(written) as *mut v128, i8x16_swizzle(octets, pack));
+
+        read += 16;
+        written += 10;
+    }
+
+    (read, written)
+}
+
+/// 12 bytes to 16 characters at a time: each three bytes are moved into a
+/// 32-bit lane, split into 6-bit indices with shifts, and looked up.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "encode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn encode_base64_simd128(alphabet: &[u8], data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let table = wasm_table::<4>(alphabet);
+    let spread = i8x16(2, 1, 0, -1, 5, 4, 3, -1, 8, 7, 6, -1, 11, 10, 9, -1);
+    let mask = u32x4_splat(0x3f);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Loads take 16 bytes, of which 12 are used.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let value = i8x16_swizzle(v128_load(data.as_ptr().add(read) as *const v128), spread);
+
+        let indices = v128_or(
+            v128_or(
+                v128_and(u32x4_shr(value, 18), mask),
+                i32x4_shl(v128_and(u32x4_shr(value, 12), mask), 8),
+            ),
+            v128_or(
+                i32x4_shl(v128_and(u32x4_shr(value, 6), mask), 16),
+                i32x4_shl(v128_and(value, mask), 24),
+            ),
+        );
+
+        v128_store(out.as_mut_ptr().add(written) as *mut v128, wasm_lookup(&table, indices));
+
+        read += 12;
+        written += 16;
+    }
+
+    (read, written)
+}
+
+/// 16 characters to 12 bytes at a time: the characters are looked up,
+/// merged into 24-bit values in 32-bit lanes, and shuffled into place.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128", feature = "decode"))]
+#[target_feature(enable = "simd128")]
+unsafe fn decode_base64_simd128(_: &[u8], lookup: Lookup, data: &[u8], out: &mut [u8]) -> (usize, usize) {
+    let lookup = lookup.with_ascii(|ascii| wasm_decode_table(ascii));
+    let pack = i8x16(2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1);
+    let mask = u32x4_splat(0xff);
+
+    let (mut read, mut written) = (0, 0);
+
+    // Stores write 16 bytes, of which 12 are kept.
+    while data.len() - read >= 16 && out.len() - written >= 16 {
+        let values = wasm_values(&lookup, v128_load(data.as_ptr().add(read) as *const v128));
+
+        if !u8x16_all_true(u8x16_lt(values, u8x16_splat(64))) {
+            break;
+        }
+
+        let value = v128_or(
+            v128_or(
+                i32x4_shl(v128_and(values, mask), 18),
+                i32x4_shl(v128_and(u32x4_shr(values, 8), mask), 12),
+            ),
+            v128_or(
+                i32x4_shl(v128_and(u32x4_shr(values, 16), mask), 6),
+                u32x4_shr(values, 24),
+            ),
+        );
+
+        v128_store(out.as_mut_ptr().add(written) as *mut v128, i8x16_swizzle(value, pack));
+
+        read += 16;
+        written += 12;
+    }
+
+    (read, written)
+}
+
+#[cfg(all(test, feature = "std", feature = "encode", feature = "decode"))]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use Base;
+
+    thread_local! {
+        /// Kernels to use on this thread instead of the detected ones.
+        pub static OVERRIDE: Cell<Option<&'static Kernels>> = const { Cell::new(None) };
+    }
+
+    fn with_kernels<R>(kernels: &'static Kernels, f: impl FnOnce() -> R) -> R {
+        OVERRIDE.with(|current| current.set(Some(kernels)));
+        let result = f();
+        OVERRIDE.with(|current| current.set(None));
+
+        result
+    }
+
+    /// Encode and decode every length up to past a few vector loops, and
+    /// decode with an invalid character at every position, with the given
+    /// kernels and with the scalar code, in every base that has kernels.
+    fn check(name: &str, kernels: &'static Kernels) {
+        let bases = Base::ALL.iter().filter(|base| matches!(base.alphabet().len(), 16 | 32 | 64));
+        let data: Vec<u8> = (0..200u32).map(|i| (i * 167 + 13) as u8).collect();
+
+        for &base in bases {
+            for len in 0..data.len() {
+                let data = &data[..len];
+                let expected = with_kernels(&SCALAR, || base.encode_raw(data));
+                let encoded = with_kernels(kernels, || base.encode_raw(data));
+
+                assert_eq!(encoded, expected, "{} encode {} {}", name, base, len);
+                assert_eq!(with_kernels(kernels, || base.decode_raw(&encoded)).as_deref(), Ok(data),
+                           "{} decode {} {}", name, base, len);
+
+                for position in 0..encoded.len() {
+                    let mut invalid = encoded.clone().into_bytes();
+                    invalid[position] = b'!';
+                    let invalid = String::from_utf8(invalid).unwrap();
+
+                    assert_eq!(with_kernels(kernels, || base.decode_raw(&invalid)),
+                               with_kernels(&SCALAR, || base.decode_raw(&invalid)),
+                               "{} decode {} {} invalid at {}", name, base, len, position);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scalar_kernels() {
+        check("scalar", &SCALAR);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn ssse3_kernels() {
+        if is_x86_feature_detected!("ssse3") {
+            check("ssse3", &SSSE3);
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn avx2_kernels() {
+        if is_x86_feature_detected!("avx2") {
+            check("avx2", &AVX2);
+        }
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn neon_kernels() {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            check("neon", &NEON);
+        }
+    }
+}
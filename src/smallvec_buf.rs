@@ -0,0 +1,74 @@
+//! Inline output via the [smallvec](https://docs.rs/smallvec) crate, so
+//! short payloads such as hashes stay off the heap.
+
+use smallvec::{Array, SmallVec};
+#[cfg(feature = "decode")]
+use decodable::split;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(feature = "decode")]
+use {Decodable, Error, Result};
+use Base;
+
+/// Encode with the given base into a `SmallVec`. The output is stored
+/// inline when it fits in `A`, and spills to the heap otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, encode_smallvec};
+///
+/// let encoded = encode_smallvec::<[u8; 64], _>(Base::Base58btc, b"hello");
+///
+/// assert_eq!(&encoded[..], b"zCn8eVZg");
+/// assert!(!encoded.spilled());
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode_smallvec<A: Array<Item = u8>, T: AsRef<[u8]>>(base: Base, data: T) -> SmallVec<A> {
+    let data = data.as_ref();
+    let mut encoded = SmallVec::new();
+    encoded.resize(base.max_encoded_len(data.len()), 0);
+
+    let written = data.encode_slice(base, &mut encoded)
+        .expect("buffer is sized to the maximum encoded length");
+
+    encoded.truncate(written);
+    encoded
+}
+
+/// Decode the string into a `SmallVec`. The data is stored inline when it
+/// fits in `A`, and spills to the heap otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, decode_smallvec};
+///
+/// let (base, data) = decode_smallvec::<[u8; 32], _>("zCn8eVZg").unwrap();
+///
+/// assert_eq!(base, Base::Base58btc);
+/// assert_eq!(&data[..], b"hello");
+/// assert!(!data.spilled());
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode_smallvec<A: Array<Item = u8>, T: AsRef<str>>(data: T) -> Result<(Base, SmallVec<A>)> {
+    let data = data.as_ref();
+    let (base, _) = split(data)?;
+    let max = base.max_decoded_len(data.len());
+
+    // The maximum length overestimates for most bases, so try inline first
+    // and only spill if the data really doesn't fit.
+    let mut decoded = SmallVec::new();
+    decoded.resize(max.min(A::size()), 0);
+
+    let (base, written) = match data.decode_slice(&mut decoded) {
+        Err(Error::BufferTooSmall) if decoded.len() < max => {
+            decoded.resize(max, 0);
+            data.decode_slice(&mut decoded)?
+        }
+        result => result?,
+    };
+    decoded.truncate(written);
+
+    Ok((base, decoded))
+}
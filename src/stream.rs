@@ -0,0 +1,204 @@
+#[cfg(feature = "encode")]
+use std::io::Write;
+#[cfg(feature = "decode")]
+use std::{cmp, str};
+
+#[cfg(feature = "encode")]
+use EncodeWriter;
+#[cfg(feature = "decode")]
+use {rfc4648, DecodeError, Error, Result};
+use Base;
+
+/// Encode data pushed to it in pieces, as if it had been concatenated.
+///
+/// [Bitwise](enum.Base.html#method.is_bitwise) bases are encoded as the
+/// data comes in, keeping back at most one partial block. The others treat
+/// the data as one big number and buffer all of it until `finish`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Encoder};
+///
+/// let mut encoder = Encoder::new(Base::Base58btc);
+///
+/// encoder.push(b"hel");
+/// encoder.push(b"lo");
+///
+/// assert_eq!(encoder.finish(), "zCn8eVZg");
+/// ```
+#[cfg(feature = "encode")]
+#[derive(Debug)]
+pub struct Encoder {
+    writer: EncodeWriter<Vec<u8>>,
+}
+
+#[cfg(feature = "encode")]
+impl Encoder {
+    /// Start encoding with the given base.
+    pub fn new(base: Base) -> Encoder {
+        Encoder {
+            writer: EncodeWriter::new(base, Vec::new()),
+        }
+    }
+
+    /// Encode the next piece of data.
+    pub fn push(&mut self, data: &[u8]) {
+        self.writer.write_all(data).expect("writing to a Vec never fails");
+    }
+
+    /// Encode the rest of the data and return the text, prefix included.
+    pub fn finish(self) -> String {
+        let encoded = self.writer.finish().expect("writing to a Vec never fails");
+
+        // Prefixes are chars and alphabets are ASCII.
+        unsafe { String::from_utf8_unchecked(encoded) }
+    }
+}
+
+/// Decode multibase text pushed to it in pieces, as if it had been
+/// concatenated.
+///
+/// The base is detected from the prefix once it has come in.
+/// [Bitwise](enum.Base.html#method.is_bitwise) bases are decoded as the
+/// text comes in, keeping back at most one block, as only the last block
+/// may carry padding. The others treat the data as one big number and only
+/// decode in `finish`.
+///
+/// Error positions are byte offsets into the whole text. After an error
+/// the decoder should be discarded.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, Decoder};
+///
+/// let mut decoder = Decoder::new();
+/// let mut decoded = Vec::new();
+///
+/// decoded.extend_from_slice(decoder.push("MaGVs").unwrap());
+/// decoded.extend_from_slice(decoder.push("bG8=").unwrap());
+///
+/// assert_eq!(decoder.base(), Some(Base::Base64pad));
+///
+/// decoded.extend_from_slice(&decoder.finish().unwrap());
+///
+/// assert_eq!(decoded, b"hello");
+/// ```
+#[cfg(feature = "decode")]
+#[derive(Debug, Default)]
+pub struct Decoder {
+    base: Option<Base>,
+    text: Vec<u8>,
+    position: usize,
+    decoded: Vec<u8>,
+}
+
+#[cfg(feature = "decode")]
+impl Decoder {
+    /// Start decoding.
+    pub fn new() -> Decoder {
+        Decoder::default()
+    }
+
+    /// Get the base of the text, once its prefix has come in.
+    pub fn base(&self) -> Option<Base> {
+        self.base
+    }
+
+    /// Decode the next piece of text, returning the bytes it completed.
+    pub fn push(&mut self, text: &str) -> Result<&[u8]> {
+        self.push_bytes(text.as_bytes(), false)?;
+
+        Ok(&self.decoded)
+    }
+
+    /// Decode the rest of the text, returning the bytes it completed, and
+    /// check that the text as a whole was valid.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        self.push_bytes(&[], true)?;
+
+        Ok(self.decoded)
+    }
+
+    /// The bytes completed by the last piece of text.
+    pub(crate) fn decoded(&self) -> &[u8] {
+        &self.decoded
+    }
+
+    /// Decode the next piece of text, which need not be valid UTF-8 on its
+    /// own, replacing the bytes held by `decoded`.
+    pub(crate) fn push_bytes(&mut self, text: &[u8], last: bool) -> Result<()> {
+        self.text.extend_from_slice(text);
+        self.decoded.clear();
+
+        let base = match self.base {
+            Some(base) => base,
+            None => match self.read_prefix(last)? {
+                Some(base) => base,
+                None => return Ok(()),
+            },
+        };
+
+        let len = if last {
+            self.text.len()
+        } else if base.is_bitwise() {
+            let block = rfc4648::block_chars(base.alphabet());
+            self.text.len().saturating_sub(1) / block * block
+        } else {
+            return Ok(());
+        };
+
+        let text = &self.text[..len];
+
+        if let Some(i) = text.iter().position(|c| !c.is_ascii()) {
+            return Err(Error::invalid_character(text, i).offset(self.position));
+        }
+
+        if !last && base.is_padded() {
+            if let Some(i) = text.iter().position(|&c| c == rfc4648::PADDING) {
+                return Err(DecodeError::InvalidPadding { position: self.position + i }.into());
+            }
+        }
+
+        // Alphabets are ASCII, and so is the text after the check above.
+        let text = unsafe { str::from_utf8_unchecked(text) };
+
+        self.decoded.resize(base.max_raw_decoded_len(len), 0);
+
+        let written = base.decode_raw_slice(text, &mut self.decoded)
+            .map_err(|err| err.offset(self.position))?;
+        self.decoded.truncate(written);
+
+        self.text.drain(..len);
+        self.position += len;
+
+        Ok(())
+    }
+
+    /// Detect the base from the prefix once enough of the text is in.
+    fn read_prefix(&mut self, last: bool) -> Result<Option<Base>> {
+        let width = match self.text.first() {
+            Some(0x00..=0x7f) => 1,
+            Some(0xc0..=0xdf) => 2,
+            Some(0xe0..=0xef) => 3,
+            Some(_) => 4,
+            None if last => return Err(Error::EmptyInput),
+            None => return Ok(None),
+        };
+
+        if self.text.len() < width && !last {
+            return Ok(None);
+        }
+
+        let prefix = &self.text[..cmp::min(width, self.text.len())];
+        let code = str::from_utf8(prefix)?.chars().next().expect("the prefix is not empty");
+        let base = Base::from_code(code)?;
+
+        self.text.drain(..width);
+        self.position = width;
+        self.base = Some(base);
+
+        Ok(Some(base))
+    }
+}
@@ -0,0 +1,184 @@
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use {validate, Base, Decodable, Error, Result};
+#[cfg(feature = "encode")]
+use Encodable;
+
+/// An owned string that is known to hold valid multibase.
+///
+/// The text is validated once on construction; the data is only decoded
+/// when asked for. Derefs to `MultibaseStr` for the accessors.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, MultibaseString};
+///
+/// let id: MultibaseString = "zCn8eVZg".parse().unwrap();
+///
+/// assert_eq!(id.base(), Base::Base58btc);
+/// assert_eq!(id.decode(), b"hello");
+/// assert_eq!(id.to_string(), "zCn8eVZg");
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug)]
+pub struct MultibaseString {
+    text: String,
+}
+
+impl MultibaseString {
+    /// Validate a string as multibase.
+    pub fn new(text: String) -> Result<MultibaseString> {
+        validate(&text)?;
+
+        Ok(MultibaseString { text })
+    }
+
+    /// Encode the data with the given base.
+    #[cfg(all(feature = "encode", feature = "decode"))]
+    pub fn encode<T: Encodable>(base: Base, data: T) -> MultibaseString {
+        MultibaseString {
+            text: data.encode(base),
+        }
+    }
+
+    /// Decode the data.
+    ///
+    /// Shadows `Decodable::decode`, which applies through `AsRef<str>`.
+    #[inline]
+    pub fn decode(&self) -> Vec<u8> {
+        (**self).decode()
+    }
+
+    /// Unwrap the encoded text.
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl FromStr for MultibaseString {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<MultibaseString> {
+        MultibaseString::new(text.to_string())
+    }
+}
+
+impl TryFrom<String> for MultibaseString {
+    type Error = Error;
+
+    fn try_from(text: String) -> Result<MultibaseString> {
+        MultibaseString::new(text)
+    }
+}
+
+impl fmt::Display for MultibaseString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for MultibaseString {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<MultibaseString> for String {
+    fn from(string: MultibaseString) -> String {
+        string.text
+    }
+}
+
+impl Deref for MultibaseString {
+    type Target = MultibaseStr;
+
+    fn deref(&self) -> &MultibaseStr {
+        MultibaseStr::from_str_unchecked(&self.text)
+    }
+}
+
+impl Borrow<MultibaseStr> for MultibaseString {
+    fn borrow(&self) -> &MultibaseStr {
+        self
+    }
+}
+
+/// A borrowed string slice that is known to hold valid multibase, the
+/// `str` to `MultibaseString`'s `String`.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::{Base, MultibaseStr};
+///
+/// let id = MultibaseStr::new("zCn8eVZg").unwrap();
+///
+/// assert_eq!(id.base(), Base::Base58btc);
+/// assert_eq!(id.decode(), b"hello");
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(transparent)]
+pub struct MultibaseStr {
+    text: str,
+}
+
+impl MultibaseStr {
+    /// Validate a string slice as multibase.
+    pub fn new(text: &str) -> Result<&MultibaseStr> {
+        validate(text)?;
+
+        Ok(MultibaseStr::from_str_unchecked(text))
+    }
+
+    fn from_str_unchecked(text: &str) -> &MultibaseStr {
+        // `MultibaseStr` is a transparent wrapper around `str`.
+        unsafe { &*(text as *const str as *const MultibaseStr) }
+    }
+
+    /// Get the base the text is encoded with.
+    pub fn base(&self) -> Base {
+        let code = self.text.chars().next().expect("multibase strings are never empty");
+
+        Base::from_code(code).expect("the prefix was validated on construction")
+    }
+
+    /// Decode the data.
+    pub fn decode(&self) -> Vec<u8> {
+        self.text.decode().expect("the text was validated on construction").into_bytes()
+    }
+
+    /// Get the encoded text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl ToOwned for MultibaseStr {
+    type Owned = MultibaseString;
+
+    fn to_owned(&self) -> MultibaseString {
+        MultibaseString {
+            text: self.text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for MultibaseStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl AsRef<str> for MultibaseStr {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
@@ -0,0 +1,174 @@
+//! A line-based framing codec for
+//! [tokio-util](https://docs.rs/tokio-util), one multibase string per line.
+
+use std::io;
+#[cfg(feature = "decode")]
+use std::str;
+
+#[cfg(feature = "decode")]
+use bytes::Buf;
+#[cfg(feature = "encode")]
+use bytes::BufMut;
+use bytes::BytesMut;
+use tokio_util::codec;
+
+use Base;
+#[cfg(feature = "encode")]
+use Encodable;
+#[cfg(feature = "decode")]
+use {Decodable, Decoded, Error};
+
+/// Encodes every outgoing frame as a multibase string followed by a
+/// newline, and decodes every incoming line as multibase, in any base.
+///
+/// Lines may end in `\r\n`. Lines that fail to decode are reported as
+/// `io::ErrorKind::InvalidData`, wrapping the `Error`. So are lines longer
+/// than the maximum length set with
+/// [`with_max_length`](#method.with_max_length), with
+/// `Error::InvalidLength`, after which the rest of the line is skipped.
+///
+/// # Examples
+///
+/// ```
+/// extern crate bytes;
+/// extern crate multibase;
+/// extern crate tokio_util;
+///
+/// use bytes::BytesMut;
+/// use multibase::{Base, LineCodec};
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = LineCodec::new(Base::Base58btc);
+/// let mut buffer = BytesMut::new();
+///
+/// codec.encode(b"hello", &mut buffer).unwrap();
+/// assert_eq!(&buffer[..], b"zCn8eVZg\n");
+///
+/// let decoded = codec.decode(&mut buffer).unwrap().unwrap();
+/// assert_eq!(decoded, (Base::Base58btc, b"hello".to_vec()));
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct LineCodec {
+    base: Base,
+    max_length: usize,
+    /// How far the buffer has been searched for a newline.
+    next_index: usize,
+    /// Whether the rest of a line that is too long is being skipped.
+    discarding: bool,
+}
+
+impl LineCodec {
+    /// Create a codec that encodes outgoing frames with the given base, and
+    /// accepts incoming lines of any length.
+    pub fn new(base: Base) -> LineCodec {
+        LineCodec::with_max_length(base, usize::MAX)
+    }
+
+    /// Create a codec that encodes outgoing frames with the given base, and
+    /// fails on incoming lines longer than `max_length` bytes, not counting
+    /// the newline. Use this with untrusted peers, so that a line that never
+    /// ends can't be buffered without bound.
+    pub fn with_max_length(base: Base, max_length: usize) -> LineCodec {
+        LineCodec {
+            base,
+            max_length,
+            next_index: 0,
+            discarding: false,
+        }
+    }
+
+    /// Get the base outgoing frames are encoded with.
+    pub fn base(&self) -> Base {
+        self.base
+    }
+
+    /// Get the maximum length of incoming lines.
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+#[cfg(feature = "decode")]
+fn decode_line(line: &[u8]) -> io::Result<Decoded> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+    str::from_utf8(line)
+        .map_err(Error::from)
+        .and_then(|line| line.decode())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(feature = "encode")]
+impl<T: AsRef<[u8]>> codec::Encoder<T> for LineCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, data: T, dst: &mut BytesMut) -> io::Result<()> {
+        let data = data.as_ref();
+        let mut encoded = Vec::with_capacity(self.base.max_encoded_len(data.len()) + 1);
+
+        data.encode_into(self.base, &mut encoded);
+        encoded.push(b'\n');
+        dst.put_slice(&encoded);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "decode")]
+impl codec::Decoder for LineCodec {
+    type Item = Decoded;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Decoded>> {
+        loop {
+            // Only search what hasn't been searched yet, and no further than
+            // a newline right after the longest line.
+            let read_to = src.len().min(self.max_length.saturating_add(1));
+            let newline = src[self.next_index..read_to].iter().position(|&c| c == b'\n');
+
+            match (self.discarding, newline) {
+                (true, Some(offset)) => {
+                    src.advance(self.next_index + offset + 1);
+                    self.next_index = 0;
+                    self.discarding = false;
+                }
+                (true, None) => {
+                    src.advance(read_to);
+                    self.next_index = 0;
+
+                    if src.is_empty() {
+                        return Ok(None);
+                    }
+                }
+                (false, Some(offset)) => {
+                    let end = self.next_index + offset;
+                    let line = src.split_to(end + 1);
+                    self.next_index = 0;
+
+                    return decode_line(&line[..end]).map(Some);
+                }
+                (false, None) if src.len() > self.max_length => {
+                    self.discarding = true;
+
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, Error::InvalidLength));
+                }
+                (false, None) => {
+                    self.next_index = read_to;
+
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<Decoded>> {
+        match self.decode(src)? {
+            Some(decoded) => Ok(Some(decoded)),
+            None if src.is_empty() => Ok(None),
+            None => {
+                self.next_index = 0;
+                decode_line(&src.split()).map(Some)
+            }
+        }
+    }
+}
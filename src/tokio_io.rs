@@ -0,0 +1,223 @@
+//! Async streaming adapters for [tokio](https://docs.rs/tokio), mirroring
+//! `EncodeWriter` and `DecodeReader`.
+
+use std::io;
+#[cfg(feature = "encode")]
+use std::io::Write;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[cfg(feature = "encode")]
+use tokio::io::AsyncWrite;
+#[cfg(feature = "decode")]
+use tokio::io::{AsyncRead, ReadBuf};
+
+#[cfg(feature = "decode")]
+use io::CHUNK;
+#[cfg(feature = "encode")]
+use EncodeWriter;
+#[cfg(feature = "decode")]
+use Decoder;
+use Base;
+
+/// Encode bytes written to it with the given base, writing the multibase
+/// text to an inner `AsyncWrite`. The async counterpart of `EncodeWriter`.
+///
+/// Encoded text is buffered until the inner writer takes it, a chunk at a
+/// time for bitwise bases. Shutting the adapter down encodes whatever is
+/// left, padding included, before shutting the inner writer down.
+///
+/// # Examples
+///
+/// ```
+/// extern crate multibase;
+/// extern crate tokio;
+///
+/// use multibase::{AsyncEncodeWriter, Base};
+/// use tokio::io::AsyncWriteExt;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let mut writer = AsyncEncodeWriter::new(Base::Base64pad, Vec::new());
+///
+/// runtime.block_on(writer.write_all(b"hello")).unwrap();
+/// runtime.block_on(writer.shutdown()).unwrap();
+///
+/// assert_eq!(writer.into_inner(), b"MaGVsbG8=");
+/// ```
+#[cfg(feature = "encode")]
+#[derive(Debug)]
+pub struct AsyncEncodeWriter<W> {
+    inner: W,
+    encoder: EncodeWriter<Vec<u8>>,
+    flushed: usize,
+    finished: bool,
+}
+
+#[cfg(feature = "encode")]
+impl<W: AsyncWrite + Unpin> AsyncEncodeWriter<W> {
+    /// Wrap a writer, encoding with the given base.
+    pub fn new(base: Base, inner: W) -> AsyncEncodeWriter<W> {
+        AsyncEncodeWriter {
+            inner,
+            encoder: EncodeWriter::new(base, Vec::new()),
+            flushed: 0,
+            finished: false,
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Unwrap the inner writer. Unless the adapter was shut down first, the
+    /// tail of the output is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Write out the text encoded so far.
+    fn poll_drain(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+        let encoded = self.encoder.get_mut();
+
+        while self.flushed < encoded.len() {
+            match Pin::new(&mut self.inner).poll_write(cx, &encoded[self.flushed..]) {
+                Poll::Ready(Ok(0)) => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                Poll::Ready(Ok(written)) => self.flushed += written,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        encoded.clear();
+        self.flushed = 0;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "encode")]
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncEncodeWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, data: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(this.encoder.write(data)),
+            poll => poll.map(|result| result.map(|_| 0)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_flush(cx),
+            poll => poll,
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if !this.finished {
+            this.encoder.write_tail()?;
+            this.finished = true;
+        }
+
+        match this.poll_drain(cx) {
+            Poll::Ready(Ok(())) => Pin::new(&mut this.inner).poll_shutdown(cx),
+            poll => poll,
+        }
+    }
+}
+
+/// Decode multibase text read from an inner `AsyncRead`, reading the
+/// decoded bytes out of it. The async counterpart of `DecodeReader`.
+///
+/// Invalid text fails the read with `io::ErrorKind::InvalidData`, wrapping
+/// the `Error`.
+///
+/// # Examples
+///
+/// ```
+/// extern crate multibase;
+/// extern crate tokio;
+///
+/// use multibase::{AsyncDecodeReader, Base};
+/// use tokio::io::AsyncReadExt;
+///
+/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// let mut reader = AsyncDecodeReader::new(&b"MaGVsbG8="[..]);
+/// let mut decoded = Vec::new();
+///
+/// runtime.block_on(reader.read_to_end(&mut decoded)).unwrap();
+///
+/// assert_eq!(reader.base(), Some(Base::Base64pad));
+/// assert_eq!(decoded, b"hello");
+/// ```
+#[cfg(feature = "decode")]
+#[derive(Debug)]
+pub struct AsyncDecodeReader<R> {
+    inner: R,
+    decoder: Decoder,
+    read: usize,
+    eof: bool,
+}
+
+#[cfg(feature = "decode")]
+impl<R: AsyncRead + Unpin> AsyncDecodeReader<R> {
+    /// Wrap a reader of multibase text.
+    pub fn new(inner: R) -> AsyncDecodeReader<R> {
+        AsyncDecodeReader {
+            inner,
+            decoder: Decoder::new(),
+            read: 0,
+            eof: false,
+        }
+    }
+
+    /// Get the base of the text, once its prefix has been read.
+    pub fn base(&self) -> Option<Base> {
+        self.decoder.base()
+    }
+
+    /// Get a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "decode")]
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncDecodeReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context, buf: &mut ReadBuf) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        while this.read == this.decoder.decoded().len() {
+            if this.eof {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut text = [0; CHUNK];
+            let mut text = ReadBuf::new(&mut text);
+
+            match Pin::new(&mut this.inner).poll_read(cx, &mut text) {
+                Poll::Ready(Ok(())) => {}
+                poll => return poll,
+            }
+
+            this.eof = text.filled().is_empty();
+            this.read = 0;
+
+            this.decoder.push_bytes(text.filled(), this.eof)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+
+        let decoded = &this.decoder.decoded()[this.read..];
+        let len = decoded.len().min(buf.remaining());
+
+        buf.put_slice(&decoded[..len]);
+        this.read += len;
+
+        Poll::Ready(Ok(()))
+    }
+}
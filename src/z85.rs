@@ -0,0 +1,117 @@
+//! [Z85](https://rfc.zeromq.org/spec/32/), the ZeroMQ flavour of base85.
+//!
+//! Z85 has no multibase code, so the output carries no prefix. Input is
+//! processed in blocks: encoding requires a multiple of 4 bytes and
+//! decoding a multiple of 5 characters.
+
+#[cfg(feature = "encode")]
+use alloc::string::String;
+#[cfg(feature = "decode")]
+use alloc::vec::Vec;
+
+use codec::Codec;
+use {Error, Result};
+#[cfg(feature = "decode")]
+use DecodeError;
+
+const ALPHABET: &[u8; 85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Encode the data as Z85.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::z85;
+///
+/// assert_eq!(z85::encode([0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]).unwrap(),
+///            "HelloWorld");
+/// ```
+#[cfg(feature = "encode")]
+pub fn encode<T: AsRef<[u8]>>(data: T) -> Result<String> {
+    let data = data.as_ref();
+
+    if data.len() % 4 != 0 {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut encoded = String::with_capacity(data.len() / 4 * 5);
+
+    for block in data.chunks(4) {
+        let mut value = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+        let mut chars = [0u8; 5];
+
+        for c in chars.iter_mut().rev() {
+            *c = ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+
+        for &c in chars.iter() {
+            encoded.push(c as char);
+        }
+    }
+
+    Ok(encoded)
+}
+
+/// Decode a Z85 string.
+///
+/// # Examples
+///
+/// ```
+/// use multibase::z85;
+///
+/// assert_eq!(z85::decode("HelloWorld").unwrap(),
+///            vec![0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b]);
+/// ```
+#[cfg(feature = "decode")]
+pub fn decode<T: AsRef<str>>(data: T) -> Result<Vec<u8>> {
+    let data = data.as_ref().as_bytes();
+
+    if data.len() % 5 != 0 {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut decoded = Vec::with_capacity(data.len() / 5 * 4);
+
+    for (i, block) in data.chunks(5).enumerate() {
+        let mut value = 0u64;
+
+        for (j, &c) in block.iter().enumerate() {
+            let digit = ALPHABET.iter().position(|&a| a == c)
+                .ok_or_else(|| Error::invalid_character(data, i * 5 + j))?;
+            value = value * 85 + digit as u64;
+        }
+
+        if value > u64::from(u32::MAX) {
+            return Err(DecodeError::Overflow { position: i * 5 }.into());
+        }
+
+        decoded.extend_from_slice(&(value as u32).to_be_bytes());
+    }
+
+    Ok(decoded)
+}
+
+/// The Z85 encoding as a runtime `Codec`.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Z85;
+
+impl Codec for Z85 {
+    fn name(&self) -> &str {
+        "z85"
+    }
+
+    fn code(&self) -> Option<char> {
+        None
+    }
+
+    #[cfg(feature = "encode")]
+    fn encode_raw(&self, data: &[u8]) -> Result<String> {
+        encode(data)
+    }
+
+    #[cfg(feature = "decode")]
+    fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+        decode(data)
+    }
+}
@@ -1,13 +1,31 @@
 extern crate multibase;
+extern crate bs58;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "futures")]
+extern crate futures;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+extern crate postcard;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "tokio")]
+extern crate tokio;
+#[cfg(feature = "tokio-codec")]
+extern crate tokio_util;
 
 use multibase::*;
 
+#[cfg(all(feature = "base2", feature = "base32"))]
 #[test]
 fn test_bases_code() {
     assert_eq!(Base2.code(), '0');
     assert_eq!(Base32hexUpper.code(), 'V');
 }
 
+#[cfg(all(feature = "base58", feature = "base64", feature = "encode", feature = "decode"))]
 #[test]
 fn test_round_trip() {
     let slices: &[&[u8]] = &[
@@ -30,12 +48,15 @@ fn test_round_trip() {
     )
 }
 
+#[cfg(all(feature = "base2", feature = "base32"))]
 #[test]
 fn test_bases_from_code() {
     assert_eq!(Base::from_code('0').unwrap(), Base2);
     assert_eq!(Base::from_code('V').unwrap(), Base32hexUpper);
 }
 
+#[cfg(all(feature = "base2", feature = "base8", feature = "base10", feature = "base16",
+          feature = "base32", feature = "base58", feature = "base64", feature = "encode"))]
 #[test]
 fn test_encode() {
     let id = b"Decentralize everything!!";
@@ -55,9 +76,9 @@ fn test_encode() {
     let id2 = b"yes mani !";
 
     assert_eq!(encode(Base2, id2),
-               "01111001011001010111001100100000011011010110000101101110011010010010000000100\
-                001");
-    assert_eq!(encode(Base8, id2), "7171312714403326055632220041");
+               "00111100101100101011100110010000001101101011000010110111001101001001000000010\
+                0001");
+    assert_eq!(encode(Base8, id2), "7362625631006654133464440102");
     assert_eq!(encode(Base10, id2), "9573277761329450583662625");
     assert_eq!(encode(Base16, id2), "f796573206d616e692021");
     assert_eq!(encode(Base32hex, id2), "vf5in683dc5n6i811");
@@ -65,8 +86,12 @@ fn test_encode() {
     assert_eq!(encode(Base32z, id2), "hxf1zgedpcfzg1ebb");
     assert_eq!(encode(Base58flickr, id2), "Z7Pznk19XTTzBtx");
     assert_eq!(encode(Base58btc, id2), "z7paNL19xttacUY");
+
+    assert_eq!(encode(Base64url, [0xfb, 0xff]), "u-_8");
 }
 
+#[cfg(all(feature = "base2", feature = "base8", feature = "base10", feature = "base16",
+          feature = "base32", feature = "base58", feature = "decode"))]
 #[test]
 fn test_decode() {
     let id = b"Decentralize everything!!";
@@ -82,11 +107,11 @@ fn test_decode() {
 
     let id2 = b"yes mani !";
 
-    assert_eq!(decode("011110010110010101110011001000000110110101100001011011100110100100100\
-                       00000100001")
+    assert_eq!(decode("001111001011001010111001100100000011011010110000101101110011010010010\
+                       000000100001")
                .unwrap(),
                (Base2, id2.to_vec()));
-    assert_eq!(decode("7171312714403326055632220041").unwrap(),
+    assert_eq!(decode("7362625631006654133464440102").unwrap(),
                (Base8, id2.to_vec()));
     assert_eq!(decode("9573277761329450583662625").unwrap(),
                (Base10, id2.to_vec()));
@@ -105,7 +130,1843 @@ fn test_decode() {
 
     // Fails
     assert_eq!(decode("Lllll"), Err(Error::UnkownBase));
-    assert_eq!(decode("Ullll"), Err(Error::UnkownBase));
+    assert_eq!(decode("Xllll"), Err(Error::UnkownBase));
+
+    assert_eq!(decode("z7pa_L19xttacUY"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 4 })))
+}
+
+#[cfg(all(feature = "encode", feature = "decode"))]
+#[test]
+fn test_z85() {
+    let key = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+
+    assert_eq!(z85::encode(key).unwrap(), "HelloWorld");
+    assert_eq!(z85::decode("HelloWorld").unwrap(), key.to_vec());
+    assert_eq!(z85::decode(z85::encode(b"\0\0\0\0\xff\xff\xff\xff").unwrap()).unwrap(),
+               b"\0\0\0\0\xff\xff\xff\xff".to_vec());
+
+    // Fails
+    assert_eq!(z85::encode(b"abc"), Err(Error::InvalidLength));
+    assert_eq!(z85::decode("Hello"), Ok(vec![0x86, 0x4f, 0xd2, 0x6f]));
+    assert_eq!(z85::decode("HelloWorl"), Err(Error::InvalidLength));
+    assert_eq!(z85::decode("Hell~"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '~', position: 4 })));
+    assert_eq!(z85::decode("%%%%%"), Err(Error::InvalidBaseString(DecodeError::Overflow { position: 0 })));
+}
+
+#[cfg(all(feature = "check", feature = "base16", feature = "base58", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_check() {
+    let address = b"\x00\xf5\x4a\x58\x51\xe9\x37\x2b\x87\x81\x0a\x8e\x60\xcd\xd2\xe7\xcf\xd8\x0b\x6e\x31";
+
+    assert_eq!(encode_check(Base58btc, &address[..]),
+               "z1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+    assert_eq!(decode_check("z1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs").unwrap(),
+               (Base58btc, address.to_vec()));
+    assert_eq!(decode_check(encode_check(Base16, b"hello")).unwrap(),
+               (Base16, b"hello".to_vec()));
+
+    // Fails
+    assert_eq!(decode_check("z1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt"), Err(Error::InvalidChecksum));
+    assert_eq!(decode_check("z1"), Err(Error::InvalidChecksum));
+}
+
+#[cfg(all(feature = "base2", feature = "base10", feature = "base16", feature = "base32",
+          feature = "base58", feature = "decode"))]
+#[test]
+fn test_bases_status() {
+    assert_eq!(Base58btc.status(), Status::Default);
+    assert_eq!(Base2.status(), Status::Candidate);
+    assert_eq!(Base32z.status(), Status::Draft);
+
+    assert_eq!(decode_strict_spec("f68656c6c6f").unwrap(), (Base16, b"hello".to_vec()));
+    assert_eq!(decode_strict_spec("9448378203247"), Err(Error::UnstableBase));
+
+    // The base is checked before the payload
+    assert_eq!(decode_strict_spec("9hello"), Err(Error::UnstableBase));
+    assert_eq!(decode_strict_spec("z0"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '0', position: 1 })));
+}
+
+#[cfg(all(feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_padded() {
+    let cases: &[(&[u8], &str)] = &[
+        (b"", "M"),
+        (b"f", "MZg=="),
+        (b"fo", "MZm8="),
+        (b"foo", "MZm9v"),
+        (b"foob", "MZm9vYg=="),
+        (b"fooba", "MZm9vYmE="),
+        (b"foobar", "MZm9vYmFy"),
+    ];
+
+    for &(data, encoded) in cases {
+        assert_eq!(encode(Base64pad, data), encoded);
+        assert_eq!(decode(encoded).unwrap(), (Base64pad, data.to_vec()));
+    }
+
+    // Fails
+    assert_eq!(decode("MZg"), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 })));
+    assert_eq!(decode("MZ==="), Err(Error::InvalidLength));
+    assert_eq!(decode("MZm8====="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(decode("MZ=g="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 2 })));
+}
+
+#[cfg(all(feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_mime() {
+    let data: Vec<u8> = (0..=255).collect();
+    let encoded = encode_mime(&data);
+
+    assert!(encoded.split("\r\n").all(|line| line.len() <= 76));
+    assert_eq!(encoded.split("\r\n").next().unwrap().len(), 76);
+    assert_eq!(encoded.replace("\r\n", ""), encode(Base64pad, &data));
+    assert_eq!(decode_mime(&encoded).unwrap(), (Base64pad, data.clone()));
+    assert_eq!(decode_mime(encoded.replace("\r\n", "\n")).unwrap(), (Base64pad, data));
+}
+
+#[cfg(all(feature = "ascii85", feature = "encode", feature = "decode"))]
+#[test]
+fn test_ascii85() {
+    let text = b"Man is distinguished";
+
+    assert_eq!(ascii85::encode(text), "9jqo^BlbD-BleB1DJ+*+F(f,q");
+    assert_eq!(ascii85::decode("9jqo^BlbD-BleB1DJ+*+F(f,q").unwrap(), text.to_vec());
+    assert_eq!(ascii85::decode("<~9jqo^BlbD-\nBleB1DJ+*+F(f,q~>").unwrap(), text.to_vec());
+
+    assert_eq!(ascii85::encode(b"\0\0\0\0abc"), "!!!!!@:E^");
+    assert_eq!(ascii85::encode_compact(b"\0\0\0\0abc"), "z@:E^");
+    assert_eq!(ascii85::decode("z@:E^").unwrap(), b"\0\0\0\0abc".to_vec());
+    assert_eq!(ascii85::decode("!!!!!@:E^").unwrap(), b"\0\0\0\0abc".to_vec());
+
+    // Fails
+    assert_eq!(ascii85::decode("9jqo^B"), Err(Error::InvalidLength));
+    assert_eq!(ascii85::decode("9jzo^"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'z', position: 2 })));
+    assert_eq!(ascii85::decode("9jqo~"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '~', position: 4 })));
+    assert_eq!(ascii85::decode("9jqo^uuuuu"), Err(Error::InvalidBaseString(DecodeError::Overflow { position: 5 })));
+}
+
+#[cfg(all(feature = "encode", feature = "decode"))]
+#[test]
+fn test_base91() {
+    assert_eq!(base91::encode(b"test"), "fPNKd");
+    assert_eq!(base91::encode(b"Hello, world!"), ">OwJh>}A\"=r@@Y?F");
+    assert_eq!(base91::decode(">OwJh>}A\"=r@@Y?F").unwrap(), b"Hello, world!".to_vec());
+
+    let data: Vec<u8> = (0..=255).rev().collect();
+
+    for len in 0..data.len() {
+        assert_eq!(base91::decode(base91::encode(&data[..len])).unwrap(), &data[..len]);
+    }
+
+    // Fails
+    assert_eq!(base91::decode("fP-Kd"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '-', position: 2 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_str() {
+    assert_eq!(decode_str("f68656c6c6f").unwrap(), (Base16, b"hello".to_vec()));
+    assert_eq!(decode_str("M").unwrap(), (Base64pad, vec![]));
+
+    // Fails
+    assert_eq!(decode_str(""), Err(Error::EmptyInput));
+    assert_eq!(decode(""), Err(Error::EmptyInput));
+    assert_eq!(decode_str("\u{1F680}abc"), Err(Error::UnkownBase));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "encode"))]
+#[test]
+fn test_encode_into() {
+    let mut buffer = Vec::new();
+
+    assert_eq!(encode_into(Base16, b"yes", &mut buffer), 7);
+    assert_eq!(encode_into(Base64pad, b"f", &mut buffer), 5);
+    assert_eq!(buffer, b"f796573MZg==");
+
+    let mut string = String::new();
+
+    for _ in 0..2 {
+        string.clear();
+        assert_eq!(encode_into_string(Base58btc, b"yes mani !", &mut string), 15);
+        assert_eq!(string, "z7paNL19xttacUY");
+    }
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_into() {
+    let mut buffer = b"id: ".to_vec();
+
+    assert_eq!(decode_into("f796573", &mut buffer).unwrap(), (Base16, 3));
+    assert_eq!(decode_into("MZg==", &mut buffer).unwrap(), (Base64pad, 1));
+    assert_eq!(buffer, b"id: yesf");
+
+    // Fails
+    assert_eq!(decode_into("MZm9vYg=", &mut buffer), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 8 })));
+    assert_eq!(decode_into("z7pa_L19xttacUY", &mut buffer), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 4 })));
+    assert_eq!(buffer, b"id: yesf");
+}
+
+#[cfg(all(feature = "base2", feature = "base10", feature = "base58", feature = "base64",
+          feature = "encode"))]
+#[test]
+fn test_encode_slice() {
+    let mut buffer = [0; 32];
+
+    assert_eq!(encode_slice(Base58btc, b"\0\0yes mani !", &mut buffer), Ok(17));
+    assert_eq!(&buffer[..17], b"z117paNL19xttacUY");
+    assert_eq!(encode_slice(Base64pad, b"f", &mut buffer), Ok(5));
+    assert_eq!(&buffer[..5], b"MZg==");
+    assert_eq!(encode_slice(Base10, b"", &mut buffer[..1]), Ok(1));
+    assert_eq!(&buffer[..1], b"9");
+
+    // Fails
+    assert_eq!(encode_slice(Base58btc, b"yes mani !", &mut buffer[..14]), Err(Error::BufferTooSmall));
+    assert_eq!(encode_slice(Base64pad, b"f", &mut buffer[..4]), Err(Error::BufferTooSmall));
+    assert_eq!(encode_slice(Base2, b"", &mut []), Err(Error::BufferTooSmall));
+
+    // Bytes past the longest possible encoding are left alone
+    let data: Vec<u8> = (0..200u32).map(|i| (i * 37 + 11) as u8).collect();
+
+    for &base in &[Base58btc, Base58flickr, Base10] {
+        for len in 0..data.len() {
+            for &zeros in &[0, 2] {
+                let mut input = vec![0; zeros];
+                input.extend_from_slice(&data[..len]);
+
+                let mut buffer = [0xaa; 512];
+                let written = encode_slice(base, &input, &mut buffer).unwrap();
+                let max = base.max_encoded_len(input.len());
+
+                assert_eq!(&buffer[..written], encode(base, &input).as_bytes(), "{} {}", base, len);
+                assert!(written <= max);
+                assert!(buffer[max..].iter().all(|&byte| byte == 0xaa), "{} {}", base, len);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "base10", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_slice() {
+    let mut buffer = [0; 16];
+
+    assert_eq!(decode_slice("z117paNL19xttacUY", &mut buffer).unwrap(), (Base58btc, 12));
+    assert_eq!(&buffer[..12], b"\0\0yes mani !");
+    assert_eq!(decode_slice("MZg==", &mut buffer).unwrap(), (Base64pad, 1));
+    assert_eq!(&buffer[..1], b"f");
+    assert_eq!(decode_slice("9", &mut []).unwrap(), (Base10, 0));
+
+    // Fails
+    assert_eq!(decode_slice("z7paNL19xttacUY", &mut buffer[..9]), Err(Error::BufferTooSmall));
+    assert_eq!(decode_slice("MZm8=", &mut buffer[..1]), Err(Error::BufferTooSmall));
+    assert_eq!(decode_slice("z7pa_L19xttacUY", &mut buffer), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 4 })));
+}
+
+#[cfg(all(feature = "base2", feature = "base8", feature = "base10", feature = "base16",
+          feature = "base32", feature = "base58", feature = "base64", feature = "encode"))]
+#[test]
+fn test_max_len() {
+    let data: Vec<u8> = (0..=255).rev().collect();
+    let bases = [Base2, Base8, Base10, Base16, Base32, Base32z, Base58btc, Base64, Base64pad];
+
+    for &base in &bases {
+        for len in [0, 1, 2, 3, 5, 8, 32, 100, 256] {
+            for data in [&data[..len], &vec![0; len][..]] {
+                let encoded = encode(base, data);
+
+                assert!(encoded.len() <= base.max_encoded_len(len));
+                assert!(len <= base.max_decoded_len(encoded.len()));
+            }
+        }
+    }
+
+    assert_eq!(Base16.max_encoded_len(3), 7);
+    assert_eq!(Base64pad.max_encoded_len(4), 9);
+    assert_eq!(Base64pad.max_decoded_len(9), 6);
+    assert_eq!(Base58btc.max_decoded_len(0), 0);
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "base64"))]
+#[test]
+fn test_bases_from_str() {
+    assert_eq!("base58btc".parse::<Base>(), Ok(Base58btc));
+    assert_eq!("base32hexupper".parse::<Base>(), Ok(Base32hexUpper));
+    assert_eq!("base64pad".parse::<Base>(), Ok(Base64pad));
+
+    // Fails
+    assert_eq!("Base58btc".parse::<Base>(), Err(Error::UnkownBase));
+    assert_eq!("base1".parse::<Base>(), Err(Error::UnkownBase));
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "base64"))]
+#[test]
+fn test_bases_display() {
+    assert_eq!(Base58btc.to_string(), "base58btc");
+    assert_eq!(format!("{}", Base32hexUpper), "base32hexupper");
+    assert_eq!(Base64url.to_string().parse::<Base>(), Ok(Base64url));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64"))]
+#[test]
+fn test_bases_char_conversions() {
+    use std::convert::{TryFrom, TryInto};
+
+    assert_eq!(Base::try_from('z'), Ok(Base58btc));
+    assert_eq!('M'.try_into(), Ok(Base64pad));
+    assert_eq!(char::from(Base32hexUpper), 'V');
+
+    let code: char = Base16.into();
+    assert_eq!(code, 'f');
+
+    // Fails
+    assert_eq!(Base::try_from('L'), Err(Error::UnkownBase));
+}
+
+#[cfg(feature = "decode")]
+#[test]
+fn test_multibyte_prefix() {
+    let mut buffer = Vec::new();
+
+    assert_eq!(Base::from_code('\u{1F680}'), Err(Error::UnkownBase));
+    assert_eq!(decode("\u{1F680}\u{1F680}"), Err(Error::UnkownBase));
+    assert_eq!(decode_into("\u{e9}abc", &mut buffer), Err(Error::UnkownBase));
+    assert_eq!(decode_slice("\u{e9}", &mut []), Err(Error::UnkownBase));
+    assert!(buffer.is_empty());
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_bases_all() {
+    assert!(Base::ALL.contains(&Base58btc));
+    assert!(Base::ALL.contains(&Base64pad));
+
+    for (i, base) in Base::ALL.iter().enumerate() {
+        assert_eq!(Base::from_code(base.code()), Ok(*base));
+        assert!(Base::ALL[..i].iter().all(|other| other.code() != base.code()));
+        assert_eq!(decode(encode(*base, b"\0all bases")).unwrap(), (*base, b"\0all bases".to_vec()));
+    }
+}
+
+#[cfg(all(feature = "base16", feature = "base58"))]
+#[test]
+fn test_bases_name() {
+    assert_eq!(Base58btc.name(), "base58btc");
+    assert_eq!(Base16Upper.name(), "base16upper");
+
+    for base in Base::ALL {
+        assert_eq!(base.name().parse::<Base>(), Ok(*base));
+    }
+}
+
+#[cfg(all(feature = "base2", feature = "base10", feature = "base16", feature = "base32",
+          feature = "base58", feature = "base64"))]
+#[test]
+fn test_bases_properties() {
+    assert!(Base64url.is_case_sensitive());
+    assert!(!Base16Upper.is_case_sensitive());
+    assert!(!Base10.is_case_sensitive());
+
+    assert!(Base64pad.is_padded());
+    assert!(!Base64.is_padded());
+
+    assert_eq!(Base2.bits_per_char(), 1.0);
+    assert_eq!(Base32hex.bits_per_char(), 5.0);
+    assert!(Base58btc.bits_per_char() > 5.857 && Base58btc.bits_per_char() < 5.858);
+    assert_eq!(Base64pad.efficiency(), 0.75);
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_bases_encode_decode() {
+    assert_eq!(Base16.encode(b"yes"), "f796573");
+    assert_eq!(Base16.encode_raw(b"yes"), "796573");
+    assert_eq!(Base16.decode_raw("796573").unwrap(), b"yes");
+    assert_eq!(Base64pad.encode_raw(b"f"), "Zg==");
+    assert_eq!(Base64pad.decode_raw("Zg==").unwrap(), b"f");
+    assert_eq!(Base58btc.decode_raw("").unwrap(), b"");
+
+    for base in Base::ALL {
+        assert_eq!(base.encode(b"\0raw"), encode(*base, b"\0raw"));
+        assert_eq!(base.decode_raw(base.encode_raw(b"\0raw")).unwrap(), b"\0raw");
+    }
+
+    // Fails
+    assert_eq!(Base16.decode_raw("7965g3"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'g', position: 4 })));
+}
+
+#[cfg(all(feature = "base32", feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_padded_bases() {
+    let id = b"foobar";
+
+    assert_eq!(encode(Base32pad, id), "cmzxw6ytboi======");
+    assert_eq!(encode(Base32padUpper, id), "CMZXW6YTBOI======");
+    assert_eq!(encode(Base32hexpad, id), "tcpnmuoj1e8======");
+    assert_eq!(encode(Base32hexpadUpper, id), "TCPNMUOJ1E8======");
+    assert_eq!(encode(Base64urlpad, [0xfb, 0xff]), "U-_8=");
+
+    assert_eq!(decode("cmzxw6ytboi======").unwrap(), (Base32pad, id.to_vec()));
+    assert_eq!(decode("TCPNMUOJ1E8======").unwrap(), (Base32hexpadUpper, id.to_vec()));
+    assert_eq!(decode("U-_8=").unwrap(), (Base64urlpad, vec![0xfb, 0xff]));
+
+    // Fails
+    assert_eq!(decode("cmzxw6ytboi====="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 16 })));
+    assert_eq!(decode("cmzxw6ytb========"), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 9 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_codecs() {
+    use multibase::codec::{self, BaseCodec};
+
+    fn round_trip<C: BaseCodec>(data: &[u8]) -> Vec<u8> {
+        C::decode_raw(C::encode_raw(data)).unwrap()
+    }
+
+    assert_eq!(codec::Base58btc::BASE, Base58btc);
+    assert_eq!(codec::Base58btc::encode(b"hello"), "zCn8eVZg");
+    assert_eq!(codec::Base64pad::encode_raw(b"f"), "Zg==");
+    assert_eq!(codec::Base16::decode_raw("796573").unwrap(), b"yes");
+    assert_eq!(round_trip::<codec::Base32hexpad>(b"\0codec"), b"\0codec");
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_dyn_codecs() {
+    use multibase::codec::{self, Codec, Registry};
+
+    struct Reversed;
+
+    impl Codec for Reversed {
+        fn name(&self) -> &str {
+            "reversed16"
+        }
+
+        fn code(&self) -> Option<char> {
+            Some('f')
+        }
+
+        fn encode_raw(&self, data: &[u8]) -> Result<String> {
+            Ok(Base16.encode_raw(data).chars().rev().collect())
+        }
+
+        fn decode_raw(&self, data: &str) -> Result<Vec<u8>> {
+            Base16.decode_raw(data.chars().rev().collect::<String>())
+        }
+    }
+
+    let codecs: Vec<Box<dyn Codec>> = vec![Box::new(Base58btc), Box::new(codec::Base16), Box::new(z85::Z85)];
+    let encoded: Vec<String> = codecs.iter().map(|codec| codec.encode_raw(b"yes!").unwrap()).collect();
+
+    assert_eq!(encoded, ["46yaVv", "79657321", "D1z4u"]);
+    assert_eq!(z85::Z85.code(), None);
+    assert_eq!(base91::Base91.encode_raw(b"test").unwrap(), "fPNKd");
+
+    let mut registry = Registry::new();
+
+    assert_eq!(registry.get("base32pad").unwrap().code(), Some('c'));
+    assert_eq!(registry.from_code('M').unwrap().name(), "base64pad");
+    assert!(registry.get("reversed16").is_none());
+
+    registry.register(Reversed);
+
+    let (codec, data) = registry.decode("f5637").unwrap();
+    assert_eq!((codec.name(), data), ("reversed16", b"se".to_vec()));
+
+    // Fails
+    assert_eq!(registry.decode("Xabc").err(), Some(Error::UnkownBase));
+    assert_eq!(registry.decode("").err(), Some(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_multibase_string() {
+    let id = MultibaseString::encode(Base32pad, b"foobar");
+
+    assert_eq!(id.as_str(), "cmzxw6ytboi======");
+    assert_eq!(id.base(), Base32pad);
+    assert_eq!(id.decode(), b"foobar");
+    assert_eq!(id, "cmzxw6ytboi======".parse().unwrap());
+    assert_eq!(format!("{}", id), "cmzxw6ytboi======");
+    assert_eq!(MultibaseString::new("f796573".to_string()).unwrap().decode(), b"yes");
+    assert_eq!(String::from(id.clone()), id.into_string());
+
+    // Fails
+    assert_eq!("".parse::<MultibaseString>(), Err(Error::EmptyInput));
+    assert_eq!("Xabc".parse::<MultibaseString>(), Err(Error::UnkownBase));
+    assert_eq!("z7pa_L19xttacUY".parse::<MultibaseString>(), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 4 })));
+}
+
+#[cfg(all(feature = "base16", feature = "encode", feature = "decode"))]
+#[test]
+fn test_multibase_str() {
+    use std::borrow::Cow;
+    use std::collections::HashSet;
+
+    fn base_of(id: &MultibaseStr) -> Base {
+        id.base()
+    }
+
+    let owned = MultibaseString::encode(Base16, b"yes");
+    let borrowed = MultibaseStr::new("f796573").unwrap();
+
+    assert_eq!(base_of(&owned), Base16);
+    assert_eq!(&*owned, borrowed);
+    assert_eq!(borrowed.to_owned(), owned);
+    assert_eq!(borrowed.as_str(), "f796573");
+    assert_eq!(borrowed.to_string(), "f796573");
+
+    let mut set = HashSet::new();
+    set.insert(owned.clone());
+    assert!(set.contains(borrowed));
+
+    let cow: Cow<MultibaseStr> = Cow::Borrowed(borrowed);
+    assert_eq!(cow.into_owned(), owned);
+
+    // Fails
+    assert_eq!(MultibaseStr::new("f79657g"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'g', position: 6 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "encode", feature = "decode"))]
+#[test]
+fn test_decoded() {
+    let decoded = decode("f796573").unwrap();
+
+    assert_eq!(decoded, Decoded { base: Base16, data: b"yes".to_vec() });
+    assert_eq!(decoded, (Base16, b"yes".to_vec()));
+    assert_eq!(decoded.as_slice(), b"yes");
+    assert_eq!(decoded.encode(Base58btc), "zhmzS");
+    assert_eq!(decoded.encode(decoded.base), "f796573");
+
+    let (base, data) = decoded.clone().into();
+    assert_eq!((base, data), (Base16, decoded.into_bytes()));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_decode_exact() {
+    let key = [7; 32];
+    let encoded = encode(Base58btc, key);
+
+    assert_eq!(decode_exact(&encoded), Ok((Base58btc, key)));
+    assert_eq!(decode_exact::<3, _>("MeWVz"), Ok((Base64pad, *b"yes")));
+    assert_eq!(decode_exact::<0, _>("f"), Ok((Base16, [])));
+
+    // Fails
+    assert_eq!(decode_exact::<31, _>(&encoded), Err(Error::InvalidLength));
+    assert_eq!(decode_exact::<33, _>(&encoded), Err(Error::InvalidLength));
+    assert_eq!(decode_exact::<2, _>("MeWVz"), Err(Error::InvalidLength));
+    assert_eq!(decode_exact::<3, _>("MeW_z"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 3 })));
+}
+
+#[cfg(all(feature = "base2", feature = "base16", feature = "base58", feature = "base64",
+          feature = "encode"))]
+#[test]
+fn test_encode_arrays_and_iterators() {
+    assert_eq!(encode(Base58btc, [0u8; 0]), "z");
+    assert_eq!(encode(Base58btc, *b"yes mani !"), "z7paNL19xttacUY");
+    assert_eq!(encode(Base16, [0xde, 0xad, 0xbe, 0xef]), "fdeadbeef");
+    assert_eq!(encode(Base16, [0xab; 40]), format!("f{}", "ab".repeat(40)));
+
+    assert_eq!(encode_iter(Base58btc, b"yes mani !".iter().cloned()), "z7paNL19xttacUY");
+    assert_eq!(encode_iter(Base64pad, vec![b'f']), "MZg==");
+    assert_eq!(encode_iter(Base16, (1..5).map(|i| i * 16)), "f10203040");
+    assert_eq!(encode_iter(Base2, None), "0");
+}
+
+#[cfg(all(feature = "bytes", feature = "base16", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_bytes() {
+    use bytes::{Bytes, BytesMut};
+
+    assert_eq!(encode(Base58btc, Bytes::from_static(b"yes mani !")), "z7paNL19xttacUY");
+    assert_eq!(encode(Base64pad, BytesMut::from("f")), "MZg==");
+
+    let (base, data) = decode_bytes("z7paNL19xttacUY").unwrap();
+    assert_eq!(base, Base58btc);
+    assert_eq!(data, Bytes::from_static(b"yes mani !"));
+
+    let mut buffer = BytesMut::new();
+    assert_eq!(decode_into_bytes_mut("f796573", &mut buffer).unwrap(), (Base16, 3));
+    assert_eq!(decode_into_bytes_mut("MZg==", &mut buffer).unwrap(), (Base64pad, 1));
+    assert_eq!(buffer, "yesf");
+
+    // Fails
+    assert_eq!(decode_bytes("MZm9vYg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 8 })));
+    assert_eq!(decode_into_bytes_mut("MZm9vYg=", &mut buffer), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 8 })));
+    assert_eq!(decode_into_bytes_mut("", &mut buffer), Err(Error::EmptyInput));
+    assert_eq!(buffer, "yesf");
+}
+
+#[cfg(all(feature = "heapless", feature = "base16", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_heapless() {
+    assert_eq!(encode_heapless::<15, _>(Base58btc, b"yes mani !").unwrap(), "z7paNL19xttacUY");
+    assert_eq!(encode_heapless::<64, _>(Base64pad, b"f").unwrap(), "MZg==");
+
+    let (base, data) = decode_heapless::<10, _>("z7paNL19xttacUY").unwrap();
+    assert_eq!(base, Base58btc);
+    assert_eq!(data, b"yes mani !");
+
+    let (base, data) = decode_heapless::<0, _>("f").unwrap();
+    assert_eq!(base, Base16);
+    assert!(data.is_empty());
+
+    // Fails
+    assert_eq!(encode_heapless::<14, _>(Base58btc, b"yes mani !"), Err(Error::BufferTooSmall));
+    assert_eq!(decode_heapless::<9, _>("z7paNL19xttacUY"), Err(Error::BufferTooSmall));
+    assert_eq!(decode_heapless::<16, _>("MZm9vYg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 8 })));
+}
+
+#[cfg(all(feature = "smallvec", feature = "base16", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_smallvec() {
+    let encoded = encode_smallvec::<[u8; 16], _>(Base58btc, b"yes mani !");
+    assert_eq!(&encoded[..], b"z7paNL19xttacUY");
+    assert!(!encoded.spilled());
+
+    let encoded = encode_smallvec::<[u8; 4], _>(Base64pad, b"f");
+    assert_eq!(&encoded[..], b"MZg==");
+    assert!(encoded.spilled());
+
+    let (base, data) = decode_smallvec::<[u8; 32], _>("z7paNL19xttacUY").unwrap();
+    assert_eq!(base, Base58btc);
+    assert_eq!(&data[..], b"yes mani !");
+    assert!(!data.spilled());
+
+    let (base, data) = decode_smallvec::<[u8; 2], _>("f796573").unwrap();
+    assert_eq!(base, Base16);
+    assert_eq!(&data[..], b"yes");
+    assert!(data.spilled());
+
+    // A hash fills the inline buffer exactly
+    let hash: Vec<u8> = (0..32).map(|i| 0xff - i).collect();
+    let (base, data) = decode_smallvec::<[u8; 32], _>(encode(Base58btc, &hash)).unwrap();
+    assert_eq!(base, Base58btc);
+    assert_eq!(&data[..], &hash[..]);
+    assert!(!data.spilled());
+
+    // Fails
+    assert_eq!(decode_smallvec::<[u8; 32], _>("MZm9vYg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 8 })));
+    assert_eq!(decode_smallvec::<[u8; 32], _>(""), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base2", feature = "base16", feature = "base32", feature = "base58",
+          feature = "base64", feature = "encode"))]
+#[test]
+fn test_display() {
+    assert_eq!(display(Base58btc, b"yes mani !").to_string(), "z7paNL19xttacUY");
+    assert_eq!(display(Base64pad, b"f").to_string(), "MZg==");
+    assert_eq!(display(Base16, b"").to_string(), "f");
+    assert_eq!(format!("<{}>", display(Base32pad, b"yes mani !")), "<cpfsxgidnmfxgsibb>");
+
+    // Longer than a single chunk
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7) as u8).collect();
+
+    for &base in &[Base58btc, Base16, Base2, Base32padUpper, Base64pad, Base64urlpad] {
+        assert_eq!(display(base, &data).to_string(), encode(base, &data));
+    }
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_raw() {
+    let mut buffer = [0; 32];
+
+    for &base in Base::ALL {
+        let encoded = encode(base, b"yes mani !");
+        let payload = &encoded[base.code().len_utf8()..];
+
+        assert_eq!(raw::encode(base, b"yes mani !"), payload);
+        assert_eq!(raw::decode(base, payload).unwrap(), b"yes mani !");
+        assert!(raw::max_encoded_len(base, 10) >= payload.len());
+        assert!(raw::max_decoded_len(base, payload.len()) >= 10);
+
+        let written = raw::decode_slice(base, payload, &mut buffer).unwrap();
+        assert_eq!(&buffer[..written], b"yes mani !");
+    }
+
+    assert_eq!(raw::encode_slice(Base58btc, b"yes mani !", &mut buffer), Ok(14));
+    assert_eq!(&buffer[..14], b"7paNL19xttacUY");
+
+    // Fails
+    assert_eq!(raw::encode_slice(Base58btc, b"yes mani !", &mut buffer[..13]), Err(Error::BufferTooSmall));
+    assert_eq!(raw::decode(Base64pad, "Zg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_base_modules() {
+    assert_eq!(base58btc::encode(b"yes mani !"), "z7paNL19xttacUY");
+    assert_eq!(base58btc::encode_raw(b"yes mani !"), "7paNL19xttacUY");
+    assert_eq!(base58btc::decode("z7paNL19xttacUY").unwrap(), b"yes mani !");
+    assert_eq!(base58btc::decode_raw("7paNL19xttacUY").unwrap(), b"yes mani !");
+
+    assert_eq!(base64pad::encode(b"f"), "MZg==");
+    assert_eq!(base32hexpadupper::decode("TCPNMU===").unwrap(), b"foo");
+
+    // Fails
+    assert_eq!(base58btc::decode("f796573"), Err(Error::WrongBase { expected: Base58btc, found: Base16 }));
+    assert_eq!(base64pad::decode_raw("Zg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_transcode() {
+    assert_eq!(transcode("z7paNL19xttacUY", Base16).unwrap(), "f796573206d616e692021");
+    assert_eq!(transcode("f796573206d616e692021", Base58btc).unwrap(), "z7paNL19xttacUY");
+    assert_eq!(transcode(String::from("MZg=="), Base64pad).unwrap(), "MZg==");
+
+    for &base in Base::ALL {
+        assert_eq!(transcode("z7paNL19xttacUY", base).unwrap(), encode(base, b"yes mani !"));
+    }
+
+    // Fails
+    assert_eq!(transcode("MZg=", Base64pad), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(transcode("", Base16), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_validate() {
+    for &base in Base::ALL {
+        for data in &[&b""[..], b"f", b"fo", b"foo", b"\0\0yes mani !"] {
+            assert_eq!(validate(encode(base, data)), Ok(base));
+        }
+    }
+
+    assert!(is_valid("z7paNL19xttacUY"));
+    assert!(is_valid("MZg=="));
+    assert!(is_valid(String::from("cmzxw6==="))); // nonzero trailing bits are tolerated
+
+    // Fails, in the same way decoding does
+    for input in &["", "Xllll", "z7paNL19xttacU0", "MZg=", "MZ===", "MZ", "MZgw==", "cmzxw6=", "cm======="] {
+        assert_eq!(validate(input), decode(input).map(|decoded| decoded.base));
+        assert!(!is_valid(input));
+    }
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "decode"))]
+#[test]
+fn test_decode_with_expected_base() {
+    assert_eq!(decode_with_expected_base("z7paNL19xttacUY", Base58btc).unwrap(), b"yes mani !");
+    assert_eq!(decode_with_expected_base(String::from("bpfsxgidnmfxgsibb"), Base32).unwrap(), b"yes mani !");
+
+    // Fails
+    let err = decode_with_expected_base("Bpfsxgidnmfxgsibb", Base32).unwrap_err();
+    assert_eq!(err, Error::WrongBase { expected: Base32, found: Base32Upper });
+    assert_eq!(err.to_string(), "Expected base32, found base32upper");
+
+    assert_eq!(decode_with_expected_base("", Base32), Err(Error::EmptyInput));
+    assert_eq!(decode_with_expected_base("bpfsxgidnmfxgsib0", Base32), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '0', position: 16 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_prefix() {
+    assert_eq!(decode_prefix("z7paNL19xttacUY").unwrap(), (Base58btc, "7paNL19xttacUY"));
+    assert_eq!(decode_prefix("MZg==").unwrap(), (Base64pad, "Zg=="));
+    assert_eq!(decode_prefix("f").unwrap(), (Base16, ""));
+
+    // The payload isn't checked
+    assert_eq!(decode_prefix("z000").unwrap(), (Base58btc, "000"));
+
+    // Fails
+    assert_eq!(decode_prefix(""), Err(Error::EmptyInput));
+    assert_eq!(decode_prefix("Xllll"), Err(Error::UnkownBase));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_batch() {
+    let items: Vec<Vec<u8>> = (0..100u8).map(|i| vec![i; i as usize]).collect();
+
+    for &base in &[Base58btc, Base64pad, Base32] {
+        let encoded = encode_many(base, &items);
+
+        for (item, encoded) in items.iter().zip(&encoded) {
+            assert_eq!(*encoded, encode(base, item));
+            assert_eq!(encoded.capacity(), encoded.len());
+        }
+
+        let decoded = decode_many(&encoded).unwrap();
+
+        for (item, decoded) in items.iter().zip(decoded) {
+            assert_eq!(decoded, (base, item.clone()));
+        }
+    }
+
+    assert!(encode_many::<&[u8]>(Base16, &[]).is_empty());
+    assert!(decode_many::<&str>(&[]).unwrap().is_empty());
+
+    // Fails
+    assert_eq!(decode_many(&["z7paNL19xttacUY", "MZg=", ""]), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+}
+
+#[cfg(all(feature = "rayon", feature = "base16", feature = "base32", feature = "base58",
+          feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_batch_par() {
+    let items: Vec<Vec<u8>> = (0..1000u32).map(|i| vec![i as u8; i as usize % 100]).collect();
+
+    for &base in &[Base58btc, Base64pad, Base32] {
+        let encoded = encode_many_par(base, &items);
+        assert_eq!(encoded, encode_many(base, &items));
+
+        assert_eq!(decode_many_par(&encoded).unwrap(), decode_many(&encoded).unwrap());
+    }
+
+    assert!(encode_many_par::<&[u8]>(Base16, &[]).is_empty());
+    assert!(decode_many_par::<&str>(&[]).unwrap().is_empty());
+
+    // Fails
+    assert_eq!(decode_many_par(&["z7paNL19xttacUY", "MZg="]), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+}
+
+#[cfg(all(feature = "rayon", feature = "base10", feature = "base58", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_bignum_par() {
+    let mut data: Vec<u8> = (0..20000u32).map(|i| (i * 7919 % 251) as u8).collect();
+
+    // Leading zeros, and runs of zeros that leave whole pieces empty.
+    for range in &[0..3, 2000..6000, 16000..20000] {
+        for byte in &mut data[range.clone()] {
+            *byte = 0;
+        }
+    }
+    data[19999] = 1;
+
+    for &len in &[4095, 4096, 5000, 20000] {
+        for &base in &[Base58btc, Base10, Base58flickr] {
+            let encoded = encode(base, &data[..len]);
+
+            assert_eq!(decode(&encoded).unwrap(), (base, data[..len].to_vec()));
+        }
+    }
+
+    let mut buffer = vec![0; 7000];
+    assert_eq!(encode_slice(Base58btc, &data[..5000], &mut buffer[..100]), Err(Error::BufferTooSmall));
+    assert_eq!(encode_slice(Base58btc, &data[..5000], &mut buffer), Ok(encode(Base58btc, &data[..5000]).len()));
+}
+
+#[cfg(all(feature = "base58", feature = "encode", feature = "decode"))]
+#[test]
+fn test_base58_matches_bs58() {
+    let alphabets = [(Base58btc, bs58::Alphabet::BITCOIN), (Base58flickr, bs58::Alphabet::FLICKR)];
+
+    for len in 0..300 {
+        let mut data: Vec<u8> = (0..len).map(|i| (i * 7919 % 251) as u8).collect();
+
+        // Leading zeros are digits of their own.
+        for byte in data.iter_mut().take(len % 4) {
+            *byte = 0;
+        }
+
+        for &(base, alphabet) in &alphabets {
+            let expected = bs58::encode(&data).with_alphabet(alphabet).into_string();
+
+            assert_eq!(base.encode_raw(&data), expected);
+            assert_eq!(base.decode_raw(&expected).unwrap(), data);
+        }
+    }
+}
+
+#[cfg(all(feature = "base10", feature = "base58", feature = "encode", feature = "decode"))]
+#[test]
+fn test_bignum_short() {
+    assert_eq!(encode(Base10, u128::MAX.to_be_bytes()), "9340282366920938463463374607431768211455");
+    assert_eq!(encode(Base10, [0, 0, 1]), "9001");
+    assert_eq!(encode(Base10, [0; 16]), "90000000000000000");
+
+    // Either side of the u128 threshold.
+    for len in 14..19 {
+        let data: Vec<u8> = (0..len).map(|i| 0xff - i as u8).collect();
+
+        for &base in &[Base10, Base58btc, Base58flickr] {
+            let encoded = encode(base, &data);
+
+            assert_eq!(decode(&encoded).unwrap(), (base, data.clone()));
+
+            let mut buffer = vec![0; encoded.len() - 1];
+            assert_eq!(encode_slice(base, &data, &mut buffer), Err(Error::BufferTooSmall));
+        }
+    }
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_encode_inline() {
+    for len in 0..64 {
+        let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+        for &base in &[Base58btc, Base32, Base64pad, Base16] {
+            let expected = encode(base, &data);
+
+            match encode_inline(base, &data) {
+                Ok(encoded) => assert_eq!(encoded, expected.as_str()),
+                Err(err) => {
+                    assert_eq!(err, Error::BufferTooSmall);
+                    assert!(expected.len() > InlineString::CAPACITY);
+                }
+            }
+        }
+    }
+
+    let encoded = encode_inline(Base58btc, b"hello").unwrap();
+
+    assert_eq!(encoded.len(), 8);
+    assert_eq!(format!("{} {:?}", encoded, encoded), "zCn8eVZg \"zCn8eVZg\"");
+    assert_eq!(encoded, encode_inline(Base58btc, b"hello").unwrap());
+    assert!(encoded < encode_inline(Base58btc, b"world").unwrap());
+    assert_eq!(decode(&*encoded).unwrap().data, b"hello");
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "decode"))]
+#[test]
+fn test_case_sensitive_lookup() {
+    assert_eq!(decode("f6869").unwrap().data, b"hi");
+    assert_eq!(decode("F6869").unwrap().data, b"hi");
+    assert_eq!(decode("bnbuq").unwrap().data, b"hi");
+    assert_eq!(decode("BNBUQ").unwrap().data, b"hi");
+
+    // Letters of the other case are not in the alphabet.
+    assert_eq!(decode("f6A"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'A', position: 2 })));
+    assert_eq!(decode("F6a"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'a', position: 2 })));
+    assert_eq!(decode("bnBUQ"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'B', position: 2 })));
+    assert_eq!(decode("BNbuq"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'b', position: 2 })));
+    assert_eq!(decode("VDa"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'a', position: 2 })));
+
+    // Neither are the characters around the ones alphabets use.
+    for &c in &['*', '[', '`', '{', '\u{7f}', '\u{ff}'] {
+        for &base in &[Base16Upper, Base32hexUpper, Base58btc, Base64url] {
+            assert!(decode(format!("{}{}", base.code(), c)).is_err());
+        }
+    }
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_context() {
+    let mut context = Context::new();
+
+    for len in (0..200).rev() {
+        let data: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+
+        for &base in &[Base58btc, Base64pad, Base32, Base16Upper] {
+            let encoded = context.encode(base, &data).to_owned();
+            assert_eq!(encoded, encode(base, &data));
+
+            assert_eq!(context.decode(&encoded).unwrap(), (base, &data[..]));
+        }
+    }
+
+    // Fails
+    assert_eq!(context.decode(""), Err(Error::EmptyInput));
+    assert_eq!(context.decode("MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn test_allocator_api() {
+    use std::alloc::System;
+
+    assert_eq!(encode_in(Base58btc, b"yes mani !", System), b"z7paNL19xttacUY");
+    assert_eq!(encode_in(Base64pad, b"f", System), b"MZg==");
+
+    let (base, data) = decode_in("z7paNL19xttacUY", System).unwrap();
+    assert_eq!(base, Base58btc);
+    assert_eq!(data, b"yes mani !");
+
+    // Fails
+    assert_eq!(decode_in("MZg=", System), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(decode_in("", System), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_try_encode_decode() {
+    assert_eq!(try_encode(Base58btc, b"yes mani !").unwrap(), "z7paNL19xttacUY");
+    assert_eq!(try_encode(Base64pad, b"f").unwrap(), "MZg==");
+    assert_eq!(try_decode("z7paNL19xttacUY").unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(try_decode(String::from("MZg==")).unwrap(), (Base64pad, b"f".to_vec()));
+
+    // Fails
+    assert_eq!(try_decode("MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(try_decode(""), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_decode_mut() {
+    for &base in Base::ALL {
+        for data in &[&b""[..], b"f", b"fo", b"foo", b"\0\0yes mani !"] {
+            let mut buffer = encode(base, data).into_bytes();
+
+            assert_eq!(decode_mut(&mut buffer), Ok(base));
+            assert_eq!(buffer, *data);
+        }
+    }
+
+    // Fails
+    assert_eq!(decode_mut(&mut b"MZg=".to_vec()), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(decode_mut(&mut b"z0".to_vec()), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '0', position: 1 })));
+    assert_eq!(decode_mut(&mut b"M\xff".to_vec()), Err(Error::InvalidBaseString(DecodeError::InvalidUtf8 { position: 1 })));
+    assert_eq!(decode_mut(&mut Vec::new()), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_guess() {
+    assert_eq!(guess::best("7paNL19xttacUY"), Some(Base58btc));
+    assert_eq!(guess::best("796573206d616e692021"), Some(Base16));
+    assert_eq!(guess::best("pfsxgidnmfxgsibb"), Some(Base32));
+    assert_eq!(guess::best("eWVz+G1hbmkgIQ=="), Some(Base64pad));
+    assert_eq!(guess::best("eWVz_G1hbmkgIQ"), Some(Base64url));
+
+    assert_eq!(guess::candidates("NBUQ===="), [Base32hexpadUpper, Base32padUpper]);
+    assert_eq!(guess::candidates("aGVsbG8="), [Base64urlpad, Base64pad]);
+    assert_eq!(guess::candidates("Zg=").len(), 0);
+
+    // Every encoding is among its own candidates
+    for &base in Base::ALL {
+        let encoded = base.encode_raw(b"yes mani !");
+        assert!(guess::candidates(&encoded).contains(&base));
+    }
+
+    assert_eq!(guess::candidates(""), []);
+    assert_eq!(guess::best("not base64!"), None);
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("z7paNL19xttacUY").unwrap(), "z7paNL19xttacUY");
+    assert_eq!(normalize("F796573").unwrap(), "f796573");
+    assert_eq!(normalize("CNBUQ====").unwrap(), "cnbuq====");
+    assert_eq!(normalize("TD1KG====").unwrap(), "td1kg====");
+    assert_eq!(normalize(String::from("MZh==")).unwrap(), "MZg==");
+
+    for &base in Base::ALL {
+        let encoded = encode(base, b"yes mani !");
+        let normalized = normalize(&encoded).unwrap();
+
+        assert_eq!(decode(&normalized).unwrap().data, b"yes mani !");
+        assert_eq!(normalize(&normalized).unwrap(), normalized);
+    }
+
+    // Fails
+    assert_eq!(normalize("MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(normalize(""), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_canonical() {
+    for &base in Base::ALL {
+        let encoded = encode(base, b"yes mani !");
+
+        assert_eq!(is_canonical(&encoded), !base.name().ends_with("upper"));
+        assert!(is_canonical(normalize(&encoded).unwrap()));
+    }
+
+    assert_eq!(check_canonical("z7paNL19xttacUY"), Ok(()));
+    assert_eq!(check_canonical("F796573"), Err(Error::NonCanonical(NonCanonical::Case)));
+    assert_eq!(check_canonical("MZh=="), Err(Error::NonCanonical(NonCanonical::TrailingBits)));
+    assert_eq!(check_canonical(String::from("cnbur====")), Err(Error::NonCanonical(NonCanonical::TrailingBits)));
+    assert_eq!(Error::NonCanonical(NonCanonical::Case).to_string(),
+               "Non-canonical encoding: uppercase variant of a case-insensitive base");
+
+    // Fails
+    assert_eq!(check_canonical("MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert!(!is_canonical(""));
+}
+
+#[cfg(all(feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_payload_eq() {
+    let encoded: Vec<String> = Base::ALL.iter().map(|&base| encode(base, b"yes mani !")).collect();
+
+    for a in &encoded {
+        for b in &encoded {
+            assert_eq!(payload_eq(a, b), Ok(true));
+        }
+
+        assert_eq!(payload_eq(a, "MZg=="), Ok(false));
+    }
+
+    assert_eq!(payload_eq("MZh==", "MZg=="), Ok(true));
+
+    // Fails
+    assert_eq!(payload_eq("MZg=", "MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(payload_eq("MZg==", ""), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_error_positions() {
+    use std::error::Error as StdError;
+
+    assert_eq!(decode("z7pa€L19"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '€', position: 4 })));
+    assert_eq!(decode("f7965 73"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: ' ', position: 5 })));
+    assert_eq!(decode("MZm9v=g=="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 5 })));
+    assert_eq!(validate("z7pa€L19"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '€', position: 4 })));
+    assert_eq!(Base58btc.decode_raw("7pa€L19"), Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '€', position: 3 })));
+
+    let err = decode("MZm9v=g==").unwrap_err();
+    assert_eq!(err.to_string(), "Invalid base string");
+    assert_eq!(err.source().unwrap().to_string(), "Invalid padding at position 5");
+
+    let err = decode("z7pa€L19").unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), "Invalid character '€' at position 4");
+    assert!(Error::EmptyInput.source().is_none());
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_lenient() {
+    assert_eq!(decode_lenient("z7paNL19xttacUY").unwrap(),
+               Lenient::Known(decode("z7paNL19xttacUY").unwrap()));
+    assert_eq!(decode_lenient("Xllll").unwrap(), Lenient::Unknown('X', "llll"));
+    assert_eq!(decode_lenient("🚀🙂").unwrap(), Lenient::Unknown('🚀', "🙂"));
+    assert_eq!(decode_lenient("\0").unwrap(), Lenient::Unknown('\0', ""));
+
+    // Fails
+    assert_eq!(decode_lenient(""), Err(Error::EmptyInput));
+    assert_eq!(decode_lenient("MZg="), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_permissive() {
+    assert_eq!(decode_permissive("0x796573206d616e692021").unwrap(), (Base16, b"yes mani !".to_vec()));
+    assert_eq!(decode_permissive("0X796573206D616E692021").unwrap(), (Base16, b"yes mani !".to_vec()));
+    assert_eq!(decode_permissive("eWVzIG1hbmkgIQ==").unwrap(), (Base64pad, b"yes mani !".to_vec()));
+    assert_eq!(decode_permissive("+//+AQ==").unwrap(), (Base64pad, vec![0xfb, 0xff, 0xfe, 1]));
+    assert_eq!(decode_permissive("-__-AQ==").unwrap(), (Base64urlpad, vec![0xfb, 0xff, 0xfe, 1]));
+    assert_eq!(decode_permissive("z7paNL19xttacUY").unwrap(), (Base58btc, b"yes mani !".to_vec()));
+
+    // Fails
+    assert_eq!(decode_permissive("0x79657g"),
+               Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'g', position: 7 })));
+    assert_eq!(decode_permissive("eWVzIG1hbmkgIQ"), Err(Error::UnkownBase));
+    assert_eq!(decode_permissive("e!Vz="), Err(Error::UnkownBase));
+    assert_eq!(decode_permissive(""), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_skip_whitespace() {
+    assert_eq!(decode_skip_whitespace("z7paNL19xttacUY").unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(decode_skip_whitespace(" z7paN L19x\tttac\r\nUY ").unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(decode_skip_whitespace("MeWVz\n  IG1h\n  bmkg\n  IQ==\n").unwrap(), (Base64pad, b"yes mani !".to_vec()));
+
+    // Fails, at positions in the original input
+    assert_eq!(decode_skip_whitespace("z7pa NL 1_"),
+               Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '_', position: 9 })));
+    assert_eq!(decode_skip_whitespace("MZg= "),
+               Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 5 })));
+    assert_eq!(decode_skip_whitespace(" \n "), Err(Error::EmptyInput));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "decode"))]
+#[test]
+fn test_decode_with() {
+    let strict = DecodeConfig::new();
+
+    for input in &["z7paNL19xttacUY", "MZg==", "MZg=", "bPFSX", "", "Xllll"] {
+        assert_eq!(decode_with(input, &strict), decode(input));
+    }
+
+    let config = DecodeConfig::new().case_insensitive(true);
+    assert_eq!(decode_with("bPFsXgIdNmFxGsIbB", &config).unwrap(), (Base32, b"yes mani !".to_vec()));
+    assert_eq!(decode_with("F796573", &config).unwrap(), (Base16Upper, b"yes".to_vec()));
+    assert_eq!(decode_with("z7PANL19xttacUY", &config).unwrap().data, decode("z7PANL19xttacUY").unwrap().data);
+
+    let config = DecodeConfig::new().allow_missing_padding(true);
+    assert_eq!(decode_with("MZg", &config).unwrap(), (Base64pad, b"f".to_vec()));
+    assert_eq!(decode_with("MZg==", &config).unwrap(), (Base64pad, b"f".to_vec()));
+    assert_eq!(decode_with("cmzxw6", &config).unwrap(), (Base32pad, b"foo".to_vec()));
+
+    let config = DecodeConfig::new().skip_whitespace(true).max_len(18);
+    assert_eq!(decode_with(" z7paNL19\nxttacUY ", &config).unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(decode_with(" z7paNL19\n xttacUY ", &config), Err(Error::InvalidLength));
+
+    let config = DecodeConfig::new().stable_only(true).allowed_bases(&[Base58btc, Base32pad, Base16]);
+    assert_eq!(decode_with("z7paNL19xttacUY", &config).unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(decode_with("MZg==", &config), Err(Error::BaseNotAllowed(Base64pad)));
+    assert_eq!(decode_with("hpfsxgidn", &config.clone().allowed_bases(&[Base32z])), Err(Error::UnstableBase));
+
+    // Fails, at positions in the original input
+    let config = DecodeConfig::new().skip_whitespace(true).case_insensitive(true);
+    assert_eq!(decode_with("bPF SX1", &config),
+               Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '1', position: 6 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_decode_canonical_only() {
+    let config = DecodeConfig::new().canonical_only(true);
+
+    assert_eq!(decode_with("z7paNL19xttacUY", &config).unwrap(), (Base58btc, b"yes mani !".to_vec()));
+    assert_eq!(decode_with("MZg==", &config).unwrap(), (Base64pad, b"f".to_vec()));
+    assert_eq!(decode_with("MZh==", &config), Err(Error::NonCanonical(NonCanonical::TrailingBits)));
+    assert_eq!(decode_with("F796573", &config), Err(Error::NonCanonical(NonCanonical::Case)));
+
+    // Tolerated input is still rejected when it isn't canonical
+    let config = config.case_insensitive(true).allow_missing_padding(true);
+    assert_eq!(decode_with("bPFSXGIDNMFXGSIBB", &config), Err(Error::NonCanonical(NonCanonical::Case)));
+    assert_eq!(decode_with("MZg", &config), Err(Error::NonCanonical(NonCanonical::Padding)));
+    assert_eq!(decode_with("bpfsxgidnmfxgsibb", &config).unwrap(), (Base32, b"yes mani !".to_vec()));
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_padding_options() {
+    assert_eq!(encode_without_padding(Base64pad, b"f"), "MZg");
+    assert_eq!(encode_without_padding(Base32pad, b"foo"), "cmzxw6");
+    assert_eq!(encode_without_padding(Base58btc, b"yes mani !"), "z7paNL19xttacUY");
+
+    let config = DecodeConfig::new().allow_missing_padding(true);
+
+    for &base in Base::ALL {
+        let encoded = encode_without_padding(base, b"yes mani !");
+        assert_eq!(decode_with(&encoded, &config).unwrap(), (base, b"yes mani !".to_vec()));
+    }
+
+    let config = DecodeConfig::new().strip_padding(true);
+    assert_eq!(decode_with("mZg==", &config), decode("mZg"));
+    assert_eq!(decode_with("MZg=", &config).unwrap(), (Base64pad, b"f".to_vec()));
+    assert_eq!(decode_with("MZg", &config).unwrap(), (Base64pad, b"f".to_vec()));
+    assert_eq!(decode_with("MZg======", &config).unwrap(), (Base64pad, b"f".to_vec()));
+
+    // Fails
+    assert_eq!(decode_with("MZg=", &DecodeConfig::new().allow_missing_padding(true)),
+               Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 4 })));
+    assert_eq!(decode_with("mZg==", &DecodeConfig::new().allow_missing_padding(true)),
+               Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '=', position: 3 })));
+}
+
+#[cfg(all(feature = "base16", feature = "base32", feature = "base58", feature = "decode"))]
+#[test]
+fn test_reject_mixed_case() {
+    let config = DecodeConfig::new().case_insensitive(true).reject_mixed_case(true);
+
+    assert_eq!(decode_with("bPFSXGIDNMFXGSIBB", &config).unwrap(), (Base32, b"yes mani !".to_vec()));
+    assert_eq!(decode_with("bpfsxgidnmfxgsibb", &config).unwrap(), (Base32, b"yes mani !".to_vec()));
+    assert_eq!(decode_with("z7paNL19xttacUY", &config).unwrap(), (Base58btc, b"yes mani !".to_vec()));
+
+    // Fails
+    assert_eq!(decode_with("bpfsxgidnmFXGSIBB", &config), Err(Error::MixedCase));
+    assert_eq!(decode_with("F79aB", &DecodeConfig::new().reject_mixed_case(true)), Err(Error::MixedCase));
+    assert_eq!(Error::MixedCase.to_string(), "Payload mixes upper and lower case");
+}
+
+#[cfg(all(feature = "base2", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode"))]
+#[test]
+fn test_encode_writer() {
+    use std::io::Write;
+
+    let data: Vec<u8> = (0..5000).map(|i| (i % 255 + 1) as u8).collect();
+
+    for &base in &[Base64pad, Base32hexpad, Base58btc, Base2] {
+        let mut writer = EncodeWriter::new(base, Vec::new());
+
+        for chunk in data.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+
+        assert_eq!(writer.finish().unwrap(), encode(base, &data).into_bytes());
+    }
+
+    let writer = EncodeWriter::new(Base64pad, Vec::new());
+    assert_eq!(writer.finish().unwrap(), b"M");
+}
+
+#[cfg(all(feature = "base2", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_decode_reader() {
+    use std::io::Read;
+
+    let data: Vec<u8> = (0..5000).map(|i| (i % 255 + 1) as u8).collect();
+
+    for &base in &[Base64pad, Base32hexpad, Base58btc, Base2] {
+        let encoded = encode(base, &data);
+        let mut reader = DecodeReader::new(encoded.as_bytes());
+        let mut decoded = Vec::new();
+
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!((reader.base(), decoded), (Some(base), data.clone()));
+    }
+
+    let invalid = |text: &str| {
+        let mut decoded = Vec::new();
+        let err = DecodeReader::new(text.as_bytes()).read_to_end(&mut decoded).unwrap_err();
+
+        err.into_inner().unwrap().downcast::<Error>().map(|err| *err).unwrap()
+    };
+
+    assert_eq!(invalid(""), Error::EmptyInput);
+    assert_eq!(invalid("MZg==MZg=="), Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 }));
+    assert_eq!(invalid("MaGVs!G8="),
+               Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '!', position: 5 }));
+}
+
+#[cfg(all(feature = "base8", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode"))]
+#[test]
+fn test_encoder() {
+    let data: Vec<u8> = (1..=100).collect();
+
+    for &base in &[Base64urlpad, Base32pad, Base58flickr, Base8] {
+        let mut encoder = Encoder::new(base);
+
+        for chunk in data.chunks(3) {
+            encoder.push(chunk);
+        }
+
+        assert_eq!(encoder.finish(), encode(base, &data));
+    }
+
+    assert_eq!(Encoder::new(Base32pad).finish(), "c");
+}
+
+#[cfg(all(feature = "base8", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_decoder() {
+    let data: Vec<u8> = (1..=100).collect();
+
+    for &base in &[Base64urlpad, Base32pad, Base58flickr, Base8] {
+        let encoded = encode(base, &data);
+        let mut decoder = Decoder::new();
+        let mut decoded = Vec::new();
+
+        for chunk in encoded.as_bytes().chunks(3) {
+            decoded.extend_from_slice(decoder.push(std::str::from_utf8(chunk).unwrap()).unwrap());
+        }
+
+        assert_eq!(decoder.base(), Some(base));
+        decoded.extend_from_slice(&decoder.finish().unwrap());
+        assert_eq!(decoded, data);
+    }
+
+    let mut decoder = Decoder::new();
+    decoder.push("MZg").unwrap();
+    assert_eq!(decoder.finish(), Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 })));
+
+    assert_eq!(Decoder::new().finish(), Err(Error::EmptyInput));
+}
+
+#[cfg(feature = "encode")]
+#[test]
+fn test_encode_chars() {
+    let data: Vec<u8> = (1..=100).collect();
+
+    for &base in Base::ALL {
+        for len in 0..8 {
+            let encoded: String = encode_chars(base, data[..len].iter().cloned()).collect();
+            assert_eq!(encoded, encode(base, &data[..len]), "{} {}", base, len);
+        }
+
+        let encoded: String = encode_chars(base, data.iter().cloned()).collect();
+        assert_eq!(encoded, encode(base, &data));
+    }
+}
+
+#[cfg(all(feature = "base58", feature = "encode"))]
+#[test]
+fn test_encode_chars_size_hint() {
+    let data: Vec<u8> = (1..=10).collect();
+
+    for &base in Base::ALL.iter().filter(|base| base.is_bitwise()) {
+        let mut chars = encode_chars(base, data.iter().cloned());
+        let mut remaining = encode(base, &data).chars().count();
+
+        loop {
+            assert_eq!(chars.size_hint(), (remaining, Some(remaining)), "{}", base);
+
+            if chars.next().is_none() {
+                break;
+            }
+
+            remaining -= 1;
+        }
+
+        assert_eq!(remaining, 0);
+    }
+
+    let mut chars = encode_chars(Base::Base58btc, data.iter().cloned());
+    assert_eq!(chars.size_hint(), (1, None));
+
+    chars.next();
+    chars.next();
+    assert_eq!(chars.size_hint(), (12, Some(12)));
+}
+
+#[cfg(all(feature = "encode", feature = "decode"))]
+#[test]
+fn test_decode_chars() {
+    let data: Vec<u8> = (1..=100).collect();
+
+    for &base in Base::ALL {
+        for len in 0..8 {
+            let encoded = encode(base, &data[..len]);
+            let decoded: Result<Vec<u8>> = decode_chars(encoded.chars()).collect();
+
+            assert_eq!(decoded.unwrap(), &data[..len], "{} {}", base, len);
+        }
+    }
+
+    for text in &["", "X", "MZg", "MZg===", "MZg=a", "MZ!==", "cmy======", "t0", "MZg==MZg=", "mZ"] {
+        let decoded: Result<Vec<u8>> = decode_chars(text.chars()).collect();
+
+        assert_eq!(decoded, decode(text).map(|decoded| decoded.data), "{}", text);
+    }
+}
+
+#[cfg(all(feature = "tokio", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_tokio_adapters() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let data: Vec<u8> = (0..5000).map(|i| (i % 255 + 1) as u8).collect();
+
+    for &base in &[Base64pad, Base32hexpad, Base58btc] {
+        let mut writer = AsyncEncodeWriter::new(base, Vec::new());
+
+        for chunk in data.chunks(7) {
+            runtime.block_on(writer.write_all(chunk)).unwrap();
+        }
+
+        runtime.block_on(writer.shutdown()).unwrap();
+
+        let encoded = writer.into_inner();
+        assert_eq!(encoded, encode(base, &data).into_bytes());
+
+        let mut reader = AsyncDecodeReader::new(&encoded[..]);
+        let mut decoded = Vec::new();
+
+        runtime.block_on(reader.read_to_end(&mut decoded)).unwrap();
+        assert_eq!((reader.base(), decoded), (Some(base), data.clone()));
+    }
+
+    let mut decoded = Vec::new();
+    let err = runtime.block_on(AsyncDecodeReader::new(&b"MZg"[..]).read_to_end(&mut decoded)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(all(feature = "futures", feature = "base32", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_streams() {
+    use futures::executor::block_on_stream;
+    use futures::stream;
+
+    let data: Vec<u8> = (0..5000).map(|i| (i % 255 + 1) as u8).collect();
+
+    for &base in &[Base64pad, Base32hexpad, Base58btc] {
+        let chunks = stream::iter(data.chunks(7).map(|chunk| chunk.to_vec()).collect::<Vec<_>>());
+        let encoded: Vec<String> = block_on_stream(encode_stream(base, chunks)).collect();
+
+        assert!(base == Base58btc || encoded.len() > 1);
+        assert_eq!(encoded.concat(), encode(base, &data));
+
+        let text = encoded.concat().into_bytes();
+        let chunks = stream::iter(text.chunks(5).map(|chunk| chunk.to_vec()).collect::<Vec<_>>());
+        let decoded: Vec<_> = block_on_stream(decode_stream(chunks)).collect::<Result<_>>().unwrap();
+
+        assert_eq!(decoded.concat(), data);
+    }
+
+    let decoded: Vec<_> = block_on_stream(decode_stream(stream::iter(vec!["MZ", "g"]))).collect();
+    assert_eq!(decoded, [Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 3 }))]);
+}
+
+#[cfg(all(feature = "tokio-codec", feature = "base16", feature = "base58", feature = "base64",
+          feature = "encode", feature = "decode"))]
+#[test]
+fn test_line_codec() {
+    use bytes::BytesMut;
+    use tokio_util::codec::{Decoder, Encoder};
+
+    let mut codec = LineCodec::new(Base64pad);
+    let mut buffer = BytesMut::new();
+
+    codec.encode(b"hello", &mut buffer).unwrap();
+    codec.encode(vec![1, 2, 3], &mut buffer).unwrap();
+    assert_eq!(&buffer[..], b"MaGVsbG8=\nMAQID\n");
+
+    buffer.extend_from_slice(b"zCn8eVZg\r\nf6869");
+
+    assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), (Base64pad, b"hello".to_vec()));
+    assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), (Base64pad, vec![1, 2, 3]));
+    assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), (Base58btc, b"hello".to_vec()));
+    assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    assert_eq!(codec.decode_eof(&mut buffer).unwrap().unwrap(), (Base16, b"hi".to_vec()));
+    assert_eq!(codec.decode_eof(&mut buffer).unwrap(), None);
+
+    buffer.extend_from_slice(b"MZg\n");
+    assert_eq!(codec.decode(&mut buffer).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+
+    // Lines arriving in pieces
+    buffer.extend_from_slice(b"MaGV");
+    assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    buffer.extend_from_slice(b"sbG8=");
+    assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    buffer.extend_from_slice(b"\n");
+    assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), (Base64pad, b"hello".to_vec()));
+
+    // Lines that are too long are skipped
+    let mut codec = LineCodec::with_max_length(Base64pad, 8);
+    let mut buffer = BytesMut::new();
+
+    buffer.extend_from_slice(b"f68656c6c");
+    let err = codec.decode(&mut buffer).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.into_inner().unwrap().downcast_ref::<Error>(), Some(&Error::InvalidLength));
+
+    buffer.extend_from_slice(b"6f\nMAQID\n");
+    assert_eq!(codec.decode(&mut buffer).unwrap().unwrap(), (Base64pad, vec![1, 2, 3]));
+    assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+    assert!(buffer.is_empty());
+
+    buffer.extend_from_slice(b"f68656c6c6f\n");
+    assert_eq!(codec.decode(&mut buffer).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(codec.decode_eof(&mut buffer).unwrap(), None);
+}
+
+#[cfg(all(feature = "base2", feature = "base8", feature = "base10", feature = "base16",
+          feature = "base32", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_power_of_two_bases_are_bitwise() {
+    let bitwise = [
+        Base2, Base8, Base16, Base16Upper,
+        Base32, Base32Upper, Base32pad, Base32padUpper, Base32hex, Base32hexUpper,
+        Base32hexpad, Base32hexpadUpper, Base32z,
+        Base64, Base64pad, Base64url, Base64urlpad,
+    ];
+    let bignum = [Base10, Base58btc, Base58flickr];
+
+    for base in &bitwise {
+        assert!(base.is_bitwise(), "{}", base);
+    }
+
+    for base in &bignum {
+        assert!(!base.is_bitwise(), "{}", base);
+    }
+
+    assert_eq!(bitwise.len() + bignum.len(), Base::ALL.len());
+
+    assert_eq!(encode(Base16, [0, 0, 1]), "f000001");
+    assert_eq!(encode(Base32, [0, 0, 1]), "baaaac");
+    assert_eq!(encode(Base64, [0, 0, 1]), "mAAAB");
+    assert_eq!(decode("mAAAB").unwrap(), (Base64, vec![0, 0, 1]));
+}
+
+#[cfg(all(feature = "base16", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_decode_lines() {
+    let text = "zCn8eVZg\n\n   MaGVsbG8=  \r\nf6869\nMZg\n";
+    let decoded: Vec<_> = decode_lines(text).collect();
+
+    assert_eq!(decoded, [
+        Ok(Decoded { base: Base58btc, data: b"hello".to_vec() }),
+        Ok(Decoded { base: Base64pad, data: b"hello".to_vec() }),
+        Ok(Decoded { base: Base16, data: b"hi".to_vec() }),
+        Err(Error::InvalidBaseString(DecodeError::InvalidPadding { position: 35 })),
+    ]);
+
+    let decoded: Result<Vec<_>> = decode_delimited("zCn8eVZg MaGVsbG8=\n\tf6869 ").collect();
+    assert_eq!(decoded.unwrap().len(), 3);
+    assert_eq!(decode_delimited(" \n ").count(), 0);
+}
+
+#[cfg(all(feature = "base32", feature = "encode", feature = "decode"))]
+#[test]
+fn test_copy_encode_decode() {
+    let data: Vec<u8> = (0..10000).map(|i| (i % 251) as u8).collect();
+    let mut encoded = Vec::new();
+
+    assert_eq!(copy_encode(&mut &data[..], &mut encoded, Base32hex).unwrap(), 10000);
+    assert_eq!(encoded, encode(Base32hex, &data).into_bytes());
+
+    let mut decoded = Vec::new();
+
+    assert_eq!(copy_decode(&mut &encoded[..], &mut decoded).unwrap(), (Base32hex, 10000));
+    assert_eq!(decoded, data);
+
+    let err = copy_decode(&mut &b""[..], &mut decoded).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(all(feature = "base10", feature = "base16", feature = "base64", feature = "encode"))]
+#[test]
+fn test_encode_reader() {
+    use std::io::Read;
+
+    let data: Vec<u8> = (0..5000).map(|i| (i % 255 + 1) as u8).collect();
+
+    for &base in &[Base64url, Base16, Base10] {
+        assert_eq!(encode_reader(base, &mut &data[..]).unwrap(), encode(base, &data));
+    }
+
+    // Short reads are followed up until the reader is exhausted.
+    let mut reader = (&data[..100]).chain(&data[100..]);
+    assert_eq!(encode_reader(Base64url, &mut reader).unwrap(), encode(Base64url, &data));
+}
+
+#[cfg(all(feature = "base32", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_decode_iter() {
+    let data: Vec<u8> = (1..=100).collect();
+
+    for &base in &[Base32z, Base64pad, Base58btc] {
+        let encoded = encode(base, &data);
+        assert_eq!(decode_iter(encoded.chars()).unwrap(), (base, data.clone()));
+    }
+
+    assert_eq!(decode_iter("".chars()), Err(Error::EmptyInput));
+    assert_eq!(decode_iter("Xabc".chars()), Err(Error::UnkownBase));
+    assert_eq!(decode_iter("MZg".chars()), decode("MZg"));
+}
+
+#[cfg(all(feature = "encode", feature = "decode"))]
+#[test]
+fn test_bitwise_blocks() {
+    let data: Vec<u8> = (0..300).map(|i| (i * 7) as u8).collect();
+
+    for &base in Base::ALL.iter().filter(|base| base.is_bitwise()) {
+        for len in 0..data.len() {
+            let encoded = encode(base, &data[..len]);
+            let mut buffer = encoded.clone().into_bytes();
+
+            assert_eq!(decode(&encoded).unwrap(), (base, data[..len].to_vec()), "{} {}", base, len);
+            assert_eq!(decode_mut(&mut buffer), Ok(base));
+            assert_eq!(buffer, &data[..len]);
+        }
+
+        let mut encoded = encode(base, &data).into_bytes();
+        encoded[100] = b'!';
+
+        assert_eq!(decode(String::from_utf8(encoded).unwrap()),
+                   Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '!', position: 100 })));
+    }
+}
+
+#[cfg(all(feature = "base10", feature = "base58", feature = "encode", feature = "decode"))]
+#[test]
+fn test_bignum_chunks() {
+    let data: Vec<u8> = (0..200).map(|i| (i * 37 + 1) as u8).collect();
+
+    for len in 1..=16 {
+        let value = data[..len].iter().fold(0u128, |value, &byte| (value << 8) | u128::from(byte));
+        assert_eq!(encode(Base10, &data[..len]), format!("9{}", value));
+    }
+
+    for &base in &[Base10, Base58btc, Base58flickr] {
+        for len in 0..data.len() {
+            let mut input = vec![0; len % 3];
+            input.extend_from_slice(&data[..len]);
+
+            let encoded = encode(base, &input);
+            let mut exact = vec![0; encoded.len()];
+
+            assert_eq!(encode_slice(base, &input, &mut exact), Ok(encoded.len()));
+            assert_eq!(exact, encoded.as_bytes());
+            assert_eq!(encode_slice(base, &input, &mut exact[1..]), Err(Error::BufferTooSmall));
+            assert_eq!(decode(encoded).unwrap(), (base, input));
+        }
+    }
+}
+
+#[cfg(all(feature = "base64", feature = "encode", feature = "decode"))]
+#[test]
+fn test_base64_long_inputs() {
+    let data = [0xfb; 48];
+
+    assert_eq!(encode(Base64, data), format!("m{}", "+/v7".repeat(16)));
+    assert_eq!(encode(Base64url, data), format!("u{}", "-_v7".repeat(16)));
+    assert_eq!(decode(format!("m{}", "+/v7".repeat(16))).unwrap(), (Base64, data.to_vec()));
+    assert_eq!(decode(format!("u{}", "-_v7".repeat(16))).unwrap(), (Base64url, data.to_vec()));
+    assert!(decode(format!("m{}", "-_v7".repeat(16))).is_err());
+
+    let data: Vec<u8> = (0..1000).map(|i| (i * 13) as u8).collect();
+
+    for &base in &[Base64, Base64pad, Base64url, Base64urlpad] {
+        let encoded: String = encode_chars(base, data.iter().cloned()).collect();
+
+        assert_eq!(encode(base, &data), encoded);
+        assert_eq!(decode(&encoded).unwrap().data, data);
+    }
+}
+
+#[cfg(all(feature = "base32", feature = "encode", feature = "decode"))]
+#[test]
+fn test_base32_long_inputs() {
+    let data: Vec<u8> = (0..1000).map(|i| (i * 13) as u8).collect();
+    let bases = [
+        Base32, Base32Upper, Base32pad, Base32padUpper, Base32hex, Base32hexUpper, Base32hexpad,
+        Base32hexpadUpper, Base32z,
+    ];
+
+    for &base in &bases {
+        let encoded: String = encode_chars(base, data.iter().cloned()).collect();
+
+        assert_eq!(encode(base, &data), encoded);
+        assert_eq!(decode(&encoded).unwrap().data, data);
+
+        let mut invalid = encoded.into_bytes();
+        invalid[500] = b'!';
+
+        assert_eq!(
+            decode(String::from_utf8(invalid).unwrap()),
+            Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: '!', position: 500 }))
+        );
+    }
+}
+
+#[cfg(all(feature = "base16", feature = "encode", feature = "decode"))]
+#[test]
+fn test_base16_long_inputs() {
+    let data: Vec<u8> = (0..1000).map(|i| (i * 13) as u8).collect();
+
+    for &base in &[Base16, Base16Upper] {
+        let encoded: String = encode_chars(base, data.iter().cloned()).collect();
+
+        assert_eq!(encode(base, &data), encoded);
+        assert_eq!(decode(&encoded).unwrap().data, data);
+
+        let mut invalid = encoded.into_bytes();
+        invalid[500] = b'g';
+
+        assert_eq!(
+            decode(String::from_utf8(invalid).unwrap()),
+            Err(Error::InvalidBaseString(DecodeError::InvalidCharacter { character: 'g', position: 500 }))
+        );
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "base32", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_serde_multibase_string() {
+    let id: MultibaseString = "MaGVsbG8=".parse().unwrap();
+    let json = serde_json::to_string(&id).unwrap();
+
+    assert_eq!(json, "\"MaGVsbG8=\"");
+    assert_eq!(serde_json::from_str::<MultibaseString>(&json).unwrap(), id);
+    assert_eq!(serde_json::from_str::<&MultibaseStr>(&json).unwrap(), &*id);
+
+    // Written in canonical form
+    for &(text, canonical) in &[("bmz", "bmy"), ("BMY", "bmy"), ("MZh==", "MZg=="), ("mZh", "mZg")] {
+        let id: MultibaseString = text.parse().unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+
+        assert_eq!(json, format!("\"{}\"", canonical));
+        assert_eq!(serde_json::to_string(&*id).unwrap(), json);
+    }
+
+    assert!(serde_json::from_str::<MultibaseString>("\"MaGVsbG8\"").is_err());
+    assert!(serde_json::from_str::<MultibaseString>("\"\"").is_err());
+    assert!(serde_json::from_str::<MultibaseString>("5").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_base() {
+    for &base in Base::ALL {
+        let name = serde_json::to_string(&base).unwrap();
+
+        assert_eq!(name, format!("\"{}\"", base.name()));
+        assert_eq!(serde_json::from_str::<Base>(&name).unwrap(), base);
+        assert_eq!(serde_json::from_str::<Base>(&format!("\"{}\"", base.code())).unwrap(), base);
+    }
+
+    assert!(serde_json::from_str::<Base>("\"base42\"").is_err());
+    assert!(serde_json::from_str::<Base>("\"!\"").is_err());
+    assert!(serde_json::from_str::<Base>("\"\"").is_err());
+}
+
+#[cfg(all(feature = "serde", feature = "base16", feature = "base58", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_serde_bytes_field() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Key {
+        #[serde(rename = "publicKeyMultibase", with = "multibase::serde::base58btc")]
+        public_key: Vec<u8>,
+    }
+
+    let key = Key { public_key: b"hello".to_vec() };
+    let json = serde_json::to_string(&key).unwrap();
+
+    assert_eq!(json, r#"{"publicKeyMultibase":"zCn8eVZg"}"#);
+    assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key);
+    assert_eq!(serde_json::from_str::<Key>(r#"{"publicKeyMultibase":"f68656c6c6f"}"#).unwrap(), key);
+    assert!(serde_json::from_str::<Key>(r#"{"publicKeyMultibase":"zCn8eVZ0"}"#).is_err());
+}
+
+#[cfg(all(feature = "serde", feature = "base58", feature = "base64", feature = "encode",
+          feature = "decode"))]
+#[test]
+fn test_serde_binary() {
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Key {
+        base: Base,
+        #[serde(with = "multibase::base_code")]
+        code: Base,
+        #[serde(with = "multibase::serde::base58btc")]
+        public_key: Vec<u8>,
+    }
+
+    let key = Key { base: Base58btc, code: Base64pad, public_key: b"hello".to_vec() };
+    let binary = postcard::to_allocvec(&key).unwrap();
+
+    assert_eq!(binary, b"\x01z\x01M\x05hello");
+    assert_eq!(postcard::from_bytes::<Key>(&binary).unwrap(), key);
+
+    assert_eq!(serde_json::to_string(&key).unwrap(),
+               r#"{"base":"base58btc","code":"M","public_key":"zCn8eVZg"}"#);
+}
+
+#[cfg(all(feature = "schemars", feature = "base58", feature = "base64", feature = "decode"))]
+#[test]
+fn test_json_schema() {
+    let schema = schemars::schema_for!(MultibaseString);
+    let pattern = schema.get("pattern").unwrap().as_str().unwrap();
 
-    assert_eq!(decode("z7pa_L19xttacUY"), Err(Error::InvalidBaseString))
+    assert!(pattern.contains("|z[1-9A-HJ-NP-Za-km-z]*|"));
+    assert!(pattern.contains("|M[+/-9A-Za-z]*=*|"));
+    assert!(pattern.ends_with("|U[\\-0-9A-Z_a-z]*=*)$"));
+    assert_eq!(schemars::schema_for!(&MultibaseStr), schema);
 }